@@ -42,7 +42,7 @@ unsafe fn check(data: &str, cstr: &CStr) {
                     assert!(uri1.hostData.ip6.is_null());
                     assert_text_eq(None, uri1.hostData.ipFuture);
                 }
-                Host::Ipv6(addr) => {
+                Host::Ipv6(addr, _) => {
                     let ptr = uri1.hostData.ip6;
                     assert!(!ptr.is_null());
                     assert_eq!((*ptr).data, addr.octets());
@@ -59,7 +59,7 @@ impl<'a> HostWrapper<'a> {
     fn unwrap(self) -> Host<'a> {
         match self {
             HostWrapper::Ipv4(addr) => Host::Ipv4(addr),
-            HostWrapper::Ipv6(addr) => Host::Ipv6(addr),
+            HostWrapper::Ipv6(addr) => Host::Ipv6(addr, None),
             HostWrapper::RegName(name) => Host::RegName(name.0),
         }
     }
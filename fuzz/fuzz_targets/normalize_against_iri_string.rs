@@ -29,7 +29,7 @@ fuzz_target!(|data: &str| {
                     return;
                 }
             }
-            Host::Ipv6(_) => return,
+            Host::Ipv6(..) => return,
             _ => {}
         }
     }
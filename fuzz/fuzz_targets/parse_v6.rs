@@ -11,7 +11,7 @@ fuzz_target!(|data: &str| {
 fn parse_v6(s: &str) -> Option<Ipv6Addr> {
     let s = format!("//[{s}]");
     match UriRef::parse(s).ok()?.authority()?.host_parsed() {
-        Host::Ipv6(addr) => Some(addr),
+        Host::Ipv6(addr, _) => Some(addr),
         _ => None,
     }
 }
@@ -4,7 +4,7 @@ pub mod state;
 
 use crate::{
     common::RiRef,
-    component::{Authority, Scheme},
+    component::{Authority, IpvFuture, Scheme},
     encoding::{
         encoder::{Fragment, Path, Port, Query, RegName, Userinfo},
         EStr,
@@ -160,6 +160,15 @@ impl BuilderInner {
             path.split_once('/').map_or(path, |x| x.0).contains(':')
         }
 
+        if let Some(auth_meta) = self.meta.auth_meta {
+            let (start, end) = auth_meta.host_bounds;
+            // The first '%' in a host can only start the zone identifier.
+            let host = &self.buf[start..end];
+            if host.find('%').is_some_and(|i| &host[i..] == "%25]") {
+                return Err(BuildError(BuildErrorKind::EmptyZoneId));
+            }
+        }
+
         let (start, end) = self.meta.path_bounds;
         let path = &self.buf[start..end];
 
@@ -361,6 +370,16 @@ impl<'a> AsHost<'a> for Ipv6Addr {
     }
 }
 
+#[cfg(all(feature = "net", feature = "zone-id"))]
+impl<'a> AsHost<'a> for (Ipv6Addr, &'a EStr<crate::encoding::encoder::ZoneId>) {
+    fn push_to(self, b: &mut BuilderInner) {
+        let (addr, zone_id) = self;
+        b.push_host(HostMeta::Ipv6(addr), |buf| {
+            write!(buf, "[{addr}%25{zone_id}]").unwrap();
+        });
+    }
+}
+
 #[cfg(feature = "net")]
 impl<'a> AsHost<'a> for IpAddr {
     fn push_to(self, b: &mut BuilderInner) {
@@ -371,6 +390,14 @@ impl<'a> AsHost<'a> for IpAddr {
     }
 }
 
+impl<'a> AsHost<'a> for IpvFuture<'a> {
+    fn push_to(self, b: &mut BuilderInner) {
+        b.push_host(HostMeta::IpvFuture, |buf| {
+            write!(buf, "[v{}.{}]", self.version(), self.address()).unwrap();
+        });
+    }
+}
+
 impl<'a> AsHost<'a> for &'a EStr<RegName> {
     fn push_to(self, b: &mut BuilderInner) {
         let meta = parser::parse_v4_or_reg_name(self.as_str().as_bytes());
@@ -383,8 +410,11 @@ impl<'a> AsHost<'a> for &'a EStr<RegName> {
 impl<R, S: To<HostEnd>> Builder<R, S> {
     /// Sets the [host] subcomponent of authority.
     ///
-    /// This method takes either an [`Ipv4Addr`], [`Ipv6Addr`], [`IpAddr`],
-    /// or <code>&amp;[EStr]&lt;[RegName]&gt;</code> as argument.
+    /// This method takes either an [`Ipv4Addr`], [`Ipv6Addr`], [`IpAddr`], [`IpvFuture`],
+    /// or <code>&amp;[EStr]&lt;[RegName]&gt;</code> as argument. With the `zone-id`
+    /// feature enabled, it also takes an `(Ipv6Addr, &EStr<ZoneId>)` pair,
+    /// which produces an IPv6 address with a zone identifier. An empty zone
+    /// identifier causes [`build`](Self::build) to fail.
     ///
     /// If the contents of an input `&EStr<RegName>` matches the
     /// `IPv4address` ABNF rule defined in [Section 3.2.2 of RFC 3986][host],
@@ -408,6 +438,7 @@ impl<R, S: To<HostEnd>> Builder<R, S> {
     ///     .build()
     ///     .unwrap();
     ///
+    /// # #[cfg(feature = "net")]
     /// assert!(matches!(uri_ref.authority().unwrap().host_parsed(), Host::Ipv4(_)));
     /// ```
     pub fn host<'a>(mut self, host: impl AsHost<'a>) -> Builder<R, HostEnd> {
@@ -495,6 +526,7 @@ impl<R: RiRef<Val = String>, S: To<End>> Builder<R, S> {
     /// - When authority is present, the path must either be empty or start with `'/'`.
     /// - When authority is not present, the path cannot start with `"//"`.
     /// - In a [relative-path reference][rel-ref], the first path segment cannot contain `':'`.
+    /// - The zone identifier of an IPv6 host, if any, cannot be empty.
     ///
     /// [rel-ref]: https://datatracker.ietf.org/doc/html/rfc3986#section-4.2
     pub fn build(self) -> Result<R, BuildError> {
@@ -3,14 +3,15 @@ pub(crate) use crate::{
         state::{NonRefStart, Start},
         Builder,
     },
-    component::{Authority, Scheme},
+    component::{Authority, Scheme, Spans},
     encoding::{
         encoder::{Fragment, Path, Query},
         EStr, Encoder,
     },
     error::{ParseError, ResolveError},
     internal::{Criteria, Meta, Parse, RiRef, Value},
-    normalizer, resolver,
+    map::{OffsetMap, Tracker},
+    normalizer, parser, resolver,
 };
 pub(crate) use alloc::{borrow::ToOwned, string::String};
 pub(crate) use borrow_or_share::{BorrowOrShare, Bos};
@@ -21,6 +22,9 @@ pub(crate) use core::{
     str::{self, FromStr},
 };
 
+#[cfg(feature = "idna")]
+pub(crate) use crate::error::IdnaError;
+
 #[cfg(feature = "serde")]
 pub(crate) use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -227,6 +231,41 @@ macro_rules! ri_maybe_ref {
             }
         }
 
+        impl<'a> $Ty<&'a str> {
+            #[doc = concat!("Parses ", $art, " ", $name, " from a string slice into ", $art, " `", $ty, "<&str>`.")]
+            ///
+            /// This function is usable in const contexts, where a panic
+            /// becomes a compile-time error.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the string does not match the
+            #[doc = concat!("[`", $abnf, "`][abnf] ABNF rule from RFC ", $rfc, ".")]
+            /// For a non-panicking variant, use [`parse`](Self::parse).
+            ///
+            #[doc = concat!("[abnf]: ", $abnf_link)]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            #[doc = concat!("use fluent_uri::", $ty, ";")]
+            ///
+            #[doc = concat!("const BASE: ", $ty, "<&str> = ", $ty, "::parse_or_panic(\"http://example.com/\");")]
+            /// assert_eq!(BASE.authority().unwrap().host(), "example.com");
+            /// ```
+            #[must_use]
+            pub const fn parse_or_panic(s: &'a str) -> Self {
+                let criteria = Criteria {
+                    must_be_ascii: $must_be_ascii,
+                    must_have_scheme: $must_have_scheme,
+                };
+                match parser::parse(s.as_bytes(), criteria) {
+                    Ok(meta) => Self { val: s, meta },
+                    Err(e) => panic!("{}", e.kind.description()),
+                }
+            }
+        }
+
         impl<T: Bos<str>> $Ty<T> {
             fn as_ref_loose(&self) -> Ref<'_, '_> {
                 self.as_ref()
@@ -422,6 +461,36 @@ macro_rules! ri_maybe_ref {
                 self.as_ref().fragment()
             }
 
+            /// Returns the byte ranges of the components.
+            ///
+            /// See the documentation of [`Spans`] for details.
+            ///
+            /// [`Spans`]: crate::component::Spans
+            ///
+            /// # Examples
+            ///
+            /// ```
+            #[doc = concat!("use fluent_uri::", $ty, ";")]
+            ///
+            /// let s = "foo://user@example.com:8042/over/there?name=ferret#nose";
+            #[doc = concat!("let ", $var, " = ", $ty, "::parse(s)?;")]
+            #[doc = concat!("let spans = ", $var, ".spans();")]
+            ///
+            /// assert_eq!(spans.scheme, Some(0..3));
+            /// assert_eq!(&s[spans.authority.unwrap()], "user@example.com:8042");
+            /// assert_eq!(&s[spans.userinfo.unwrap()], "user");
+            /// assert_eq!(&s[spans.host.unwrap()], "example.com");
+            /// assert_eq!(&s[spans.port.unwrap()], "8042");
+            /// assert_eq!(&s[spans.path], "/over/there");
+            /// assert_eq!(&s[spans.query.unwrap()], "name=ferret");
+            /// assert_eq!(&s[spans.fragment.unwrap()], "nose");
+            /// # Ok::<_, fluent_uri::error::ParseError>(())
+            /// ```
+            #[must_use]
+            pub fn spans(&self) -> Spans {
+                self.as_ref_loose().spans()
+            }
+
             $(
                 #[doc = concat!("Resolves the ", $name, " against the given base ", $nr_name)]
                 #[doc = concat!("and returns the target ", $nr_name, ".")]
@@ -485,7 +554,47 @@ macro_rules! ri_maybe_ref {
                     &self,
                     base: &$NonRefTy<U>,
                 ) -> Result<$NonRefTy<String>, ResolveError> {
-                    resolver::resolve(base.as_ref(), self.as_ref_loose()).map(RiRef::new_pair)
+                    resolver::resolve(base.as_ref(), self.as_ref_loose(), &mut ()).map(RiRef::new_pair)
+                }
+
+                #[doc = concat!("Resolves the ", $name, " against the given base ", $nr_name)]
+                #[doc = concat!("and returns the target ", $nr_name, " along with an offset map.")]
+                ///
+                /// The offset map maps byte indexes in the target to byte ranges
+                #[doc = concat!("in `self` ([`Source::Input`]) or in the base ", $nr_name, " ([`Source::Base`]).")]
+                /// See [`resolve_against`] for details on the resolution.
+                ///
+                /// [`Source::Input`]: crate::map::Source::Input
+                /// [`Source::Base`]: crate::map::Source::Base
+                /// [`resolve_against`]: Self::resolve_against
+                ///
+                /// # Errors
+                ///
+                /// Returns `Err` if and only if [`resolve_against`] does.
+                ///
+                /// # Examples
+                ///
+                /// ```
+                #[doc = concat!("use fluent_uri::{map::Source, ", stringify!($NonRefTy), ", ", $ty, "};")]
+                ///
+                #[doc = concat!("let base = ", stringify!($NonRefTy), "::parse(\"http://example.com/foo/bar\")?;")]
+                #[doc = concat!("let ", $var, " = ", $ty, "::parse(\"../baz?q\")?;")]
+                ///
+                #[doc = concat!("let (target, map) = ", $var, ".resolve_against_with_map(&base).unwrap();")]
+                /// assert_eq!(target, "http://example.com/baz?q");
+                /// // "example.com" comes from the base.
+                /// assert_eq!(map.map_range(7..18), Some((Source::Base, 7..18)));
+                /// // "baz?q" comes from the reference.
+                /// assert_eq!(map.map_range(19..24), Some((Source::Input, 3..8)));
+                /// # Ok::<_, fluent_uri::error::ParseError>(())
+                /// ```
+                pub fn resolve_against_with_map<U: Bos<str>>(
+                    &self,
+                    base: &$NonRefTy<U>,
+                ) -> Result<($NonRefTy<String>, OffsetMap), ResolveError> {
+                    let mut t = Tracker::default();
+                    let (val, meta) = resolver::resolve(base.as_ref(), self.as_ref_loose(), &mut t)?;
+                    Ok((RiRef::new(val, meta), t.finish()))
                 }
             )?
 
@@ -522,7 +631,64 @@ macro_rules! ri_maybe_ref {
             /// ```
             #[must_use]
             pub fn normalize(&self) -> $Ty<String> {
-                RiRef::new_pair(normalizer::normalize(self.as_ref_loose()))
+                RiRef::new_pair(normalizer::normalize(self.as_ref_loose(), &mut ()))
+            }
+
+            #[doc = concat!("Normalizes the ", $name, " and converts any internationalized registered name to A-labels.")]
+            ///
+            /// This method takes the steps of [`normalize`] and then, if the host is
+            /// a registered name that decodes to non-ASCII characters, replaces it with
+            /// its ASCII-compatible form as returned by [`EStr::to_ascii`].
+            ///
+            /// [`normalize`]: Self::normalize
+            /// [`EStr::to_ascii`]: crate::encoding::EStr::to_ascii
+            ///
+            /// # Errors
+            ///
+            /// Returns `Err` if the registered name does not decode to valid UTF-8
+            /// or if UTS #46 processing fails.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            #[doc = concat!("use fluent_uri::", $ty, ";")]
+            ///
+            #[doc = concat!("let ", $var, " = ", $ty, "::parse(\"HTTP://B%C3%BCcher.example/%7e\")?;")]
+            #[doc = concat!("assert_eq!(", $var, ".normalize_idna().unwrap(), \"http://xn--bcher-kva.example/~\");")]
+            /// # Ok::<_, fluent_uri::error::ParseError>(())
+            /// ```
+            #[cfg(feature = "idna")]
+            pub fn normalize_idna(&self) -> Result<$Ty<String>, IdnaError> {
+                normalizer::normalize_idna(self.as_ref_loose()).map(RiRef::new_pair)
+            }
+
+            #[doc = concat!("Normalizes the ", $name, " and returns an offset map along with it.")]
+            ///
+            /// The offset map maps byte indexes in the output to byte ranges in `self`.
+            /// See [`normalize`] for details on the normalization.
+            ///
+            /// [`normalize`]: Self::normalize
+            ///
+            /// # Examples
+            ///
+            /// ```
+            #[doc = concat!("use fluent_uri::{map::Source, ", $ty, "};")]
+            ///
+            #[doc = concat!("let ", $var, " = ", $ty, "::parse(\"HTTP://[0:0::1]/a/./%62\")?;")]
+            #[doc = concat!("let (normalized, map) = ", $var, ".normalize_with_map();")]
+            /// assert_eq!(normalized, "http://[::1]/a/b");
+            ///
+            /// // The rewritten IPv6 address maps to the original one.
+            /// assert_eq!(map.map(8), Some((Source::Input, 7..15)));
+            /// // 'b' maps to "%62".
+            /// assert_eq!(map.map(15), Some((Source::Input, 20..23)));
+            /// # Ok::<_, fluent_uri::error::ParseError>(())
+            /// ```
+            #[must_use]
+            pub fn normalize_with_map(&self) -> ($Ty<String>, OffsetMap) {
+                let mut t = Tracker::default();
+                let (val, meta) = normalizer::normalize(self.as_ref_loose(), &mut t);
+                (RiRef::new(val, meta), t.finish())
             }
 
             $(
@@ -788,13 +954,17 @@ impl<'v, 'm> Ref<'v, 'm> {
         Scheme::new_validated(self.slice(0, end))
     }
 
-    pub fn authority(&self) -> Option<Authority<'v>> {
-        let mut meta = self.meta.auth_meta?;
+    fn authority_bounds(&self) -> (usize, usize) {
         let start = match self.meta.scheme_end {
             Some(i) => i.get() + 3,
             None => 2,
         };
-        let end = self.meta.path_bounds.0;
+        (start, self.meta.path_bounds.0)
+    }
+
+    pub fn authority(&self) -> Option<Authority<'v>> {
+        let mut meta = self.meta.auth_meta?;
+        let (start, end) = self.authority_bounds();
 
         meta.host_bounds.0 -= start;
         meta.host_bounds.1 -= start;
@@ -824,6 +994,30 @@ impl<'v, 'm> Ref<'v, 'm> {
             .map(|i| self.eslice(i, self.val.len()))
     }
 
+    pub fn spans(&self) -> Spans {
+        let mut spans = Spans {
+            scheme: self.meta.scheme_end.map(|i| 0..i.get()),
+            path: self.meta.path_bounds.0..self.meta.path_bounds.1,
+            query: self
+                .meta
+                .query_end
+                .map(|i| self.meta.path_bounds.1 + 1..i.get()),
+            fragment: self.fragment_start().map(|i| i..self.val.len()),
+            ..Spans::default()
+        };
+
+        if let Some(auth_meta) = self.meta.auth_meta {
+            let (start, end) = self.authority_bounds();
+            let (host_start, host_end) = auth_meta.host_bounds;
+
+            spans.authority = Some(start..end);
+            spans.userinfo = (host_start != start).then(|| start..host_start - 1);
+            spans.host = Some(host_start..host_end);
+            spans.port = (host_end != end).then(|| host_end + 1..end);
+        }
+        spans
+    }
+
     #[inline]
     pub fn has_scheme(&self) -> bool {
         self.meta.scheme_end.is_some()
@@ -0,0 +1,316 @@
+//! Database connection strings with multi-host authorities.
+//!
+//! Connection strings such as `mongodb://h1:27017,h2:27018/db?replicaSet=x`
+//! list several hosts in the authority, each with an optional port. Such strings
+//! are not valid URIs when more than one port is given, and otherwise parse as
+//! a single registered name such as `h1,h2`. Use [`ConnectionString::parse`]
+//! or [`Authority::hosts`] to split the host list.
+
+use crate::{
+    component::{Authority, Scheme},
+    encoding::{
+        encoder::{Path, Query, Userinfo},
+        EStr,
+    },
+    error::{ParseError, ParseErrorKind},
+    internal::NoInput,
+    UriRef,
+};
+use alloc::vec::Vec;
+
+fn unexpected_char(index: usize) -> ParseError {
+    ParseError {
+        index,
+        kind: ParseErrorKind::UnexpectedChar,
+        input: NoInput,
+    }
+}
+
+/// An iterator over the hosts in a comma-separated host list.
+///
+/// This struct is created by [`Authority::hosts`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Hosts<'a> {
+    rest: Option<&'a str>,
+    offset: usize,
+}
+
+impl<'a> Iterator for Hosts<'a> {
+    type Item = Result<Authority<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.rest?;
+        let offset = self.offset;
+
+        // Commas within square brackets belong to an IPvFuture address.
+        let mut in_brackets = false;
+        let end = s.bytes().position(|x| {
+            match x {
+                b'[' => in_brackets = true,
+                b']' => in_brackets = false,
+                _ => {}
+            }
+            x == b',' && !in_brackets
+        });
+        let host = if let Some(i) = end {
+            self.rest = Some(&s[i + 1..]);
+            self.offset += i + 1;
+            &s[..i]
+        } else {
+            self.rest = None;
+            s
+        };
+
+        Some(match Authority::parse(host) {
+            Ok(auth) if auth.host().is_empty() => Err(unexpected_char(offset)),
+            Ok(auth) => match auth.userinfo() {
+                Some(userinfo) => Err(unexpected_char(offset + userinfo.len())),
+                None => Ok(auth),
+            },
+            Err(e) => Err(ParseError {
+                index: offset + e.index,
+                ..e
+            }),
+        })
+    }
+}
+
+impl<'a> Authority<'a> {
+    /// Returns an iterator over the comma-separated hosts in the authority,
+    /// each with an optional port.
+    ///
+    /// Each host is validated as an IPv4 address, an IP literal, or a registered
+    /// name, and is returned as an `Authority` without userinfo. Error indexes
+    /// are relative to the start of the authority.
+    ///
+    /// Note that a URI is only valid if at most the last host has a port.
+    /// Use [`ConnectionString::parse`] to parse a string in which other hosts
+    /// have ports as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{component::Host, Uri};
+    ///
+    /// let uri = Uri::parse("postgresql://user@h1,10.0.0.2,h2:5433/db")?;
+    /// let auth = uri.authority().unwrap();
+    /// assert_eq!(auth.host(), "h1,10.0.0.2,h2");
+    ///
+    /// let hosts = auth.hosts().collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(hosts.len(), 3);
+    /// # #[cfg(feature = "net")]
+    /// assert!(matches!(hosts[1].host_parsed(), Host::Ipv4(_)));
+    /// assert_eq!(hosts[2].host(), "h2");
+    /// assert_eq!(hosts[2].port_to_u16(), Ok(Some(5433)));
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub fn hosts(&self) -> Hosts<'a> {
+        let start = self.userinfo().map_or(0, |userinfo| userinfo.len() + 1);
+        Hosts {
+            rest: Some(&self.as_str()[start..]),
+            offset: start,
+        }
+    }
+}
+
+/// A database connection string with a multi-host authority.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::conn::ConnectionString;
+///
+/// let conn = ConnectionString::parse(
+///     "mongodb://u:p@h1:27017,h2:27018/db?replicaSet=x&w=majority",
+/// )?;
+/// assert_eq!(conn.scheme().as_str(), "mongodb");
+/// assert_eq!(conn.username().unwrap(), "u");
+/// assert_eq!(conn.password().unwrap(), "p");
+///
+/// let hosts: Vec<_> = conn
+///     .hosts()
+///     .iter()
+///     .map(|h| (h.host(), h.port_to_u16().unwrap()))
+///     .collect();
+/// assert_eq!(hosts, [("h1", Some(27017)), ("h2", Some(27018))]);
+///
+/// assert_eq!(conn.database().unwrap(), "db");
+/// assert_eq!(conn.option("replicaSet").unwrap(), "x");
+/// let options: Vec<_> = conn.options().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+/// assert_eq!(options, [("replicaSet", "x"), ("w", "majority")]);
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConnectionString<'a> {
+    scheme: &'a Scheme,
+    // The userinfo followed by an empty host.
+    userinfo: Authority<'a>,
+    hosts: Vec<Authority<'a>>,
+    path: &'a EStr<Path>,
+    query: Option<&'a EStr<Query>>,
+}
+
+impl<'a> ConnectionString<'a> {
+    /// Parses a connection string of the form
+    /// `scheme "://" [ userinfo "@" ] [ hosts ] path [ "?" query ] [ "#" fragment ]`,
+    /// where `hosts` is a comma-separated list of `host [ ":" port ]`.
+    ///
+    /// Except for the host list, the string must match the `URI` ABNF rule
+    /// from RFC 3986. The host list may be empty. A fragment, if any, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string is not a valid connection string.
+    pub fn parse(s: &'a str) -> Result<Self, ParseError> {
+        let (scheme, rest) = s.split_once(':').ok_or(unexpected_char(s.len()))?;
+        let scheme_end = scheme.len();
+        let scheme = Scheme::new(scheme).ok_or(unexpected_char(0))?;
+
+        if !rest.starts_with("//") {
+            // Point at the first character that is not a slash.
+            let i = usize::from(rest.starts_with('/'));
+            return Err(unexpected_char(scheme_end + 1 + i));
+        }
+        let auth_start = scheme_end + 3;
+        let auth_end = s[auth_start..]
+            .find(['/', '?', '#'])
+            .map_or(s.len(), |i| auth_start + i);
+        let auth = &s[auth_start..auth_end];
+
+        let (userinfo, hosts_start) = match auth.rfind('@') {
+            Some(i) => {
+                let userinfo = Authority::parse(&auth[..=i]).map_err(|e| ParseError {
+                    index: auth_start + e.index,
+                    ..e
+                })?;
+                (userinfo, i + 1)
+            }
+            None => (Authority::EMPTY, 0),
+        };
+
+        let hosts = if hosts_start == auth.len() {
+            Vec::new()
+        } else {
+            Hosts {
+                rest: Some(&auth[hosts_start..]),
+                offset: auth_start + hosts_start,
+            }
+            .collect::<Result<_, _>>()?
+        };
+
+        let rest = UriRef::parse(&s[auth_end..]).map_err(|e| ParseError {
+            index: auth_end + e.index,
+            ..e
+        })?;
+        if rest.has_authority() {
+            return Err(unexpected_char(auth_end + 1));
+        }
+
+        Ok(Self {
+            scheme,
+            userinfo,
+            hosts,
+            path: rest.path(),
+            query: rest.query(),
+        })
+    }
+
+    /// Returns the scheme.
+    #[must_use]
+    pub fn scheme(&self) -> &'a Scheme {
+        self.scheme
+    }
+
+    /// Returns the optional userinfo.
+    #[must_use]
+    pub fn userinfo(&self) -> Option<&'a EStr<Userinfo>> {
+        self.userinfo.userinfo()
+    }
+
+    /// Returns the username in the userinfo, i.e., the part before the first colon.
+    ///
+    /// See [`Authority::username`] for details.
+    #[must_use]
+    pub fn username(&self) -> Option<&'a EStr<Userinfo>> {
+        self.userinfo.username()
+    }
+
+    /// Returns the password in the userinfo, i.e., the part after the first colon.
+    ///
+    /// See [`Authority::password`] for details.
+    #[must_use]
+    pub fn password(&self) -> Option<&'a EStr<Userinfo>> {
+        self.userinfo.password()
+    }
+
+    /// Returns the hosts, each with an optional port and without userinfo.
+    #[must_use]
+    pub fn hosts(&self) -> &[Authority<'a>] {
+        &self.hosts
+    }
+
+    /// Returns the path.
+    #[must_use]
+    pub fn path(&self) -> &'a EStr<Path> {
+        self.path
+    }
+
+    /// Returns the database name, i.e., the path without its leading slash.
+    ///
+    /// Returns `None` if the path is empty or `"/"`.
+    #[must_use]
+    pub fn database(&self) -> Option<&'a EStr<Path>> {
+        let name = self
+            .path
+            .as_str()
+            .strip_prefix('/')
+            .unwrap_or(self.path.as_str());
+        (!name.is_empty()).then(|| EStr::new_validated(name))
+    }
+
+    /// Returns the optional query.
+    #[must_use]
+    pub fn query(&self) -> Option<&'a EStr<Query>> {
+        self.query
+    }
+
+    /// Returns an iterator over the `name=value` options in the query.
+    ///
+    /// Options are separated by `'&'`, and empty ones are skipped.
+    /// An option without `'='` has an empty value.
+    pub fn options(&self) -> Options<'a> {
+        Options {
+            inner: self.query.map(|query| query.as_str().split('&')),
+        }
+    }
+
+    /// Returns the value of the first option with the given name.
+    ///
+    /// Option names are percent-decoded before comparison.
+    #[must_use]
+    pub fn option(&self, name: &str) -> Option<&'a EStr<Query>> {
+        self.options()
+            .find(|(key, _)| key.decode().as_bytes() == name.as_bytes())
+            .map(|(_, value)| value)
+    }
+}
+
+/// An iterator over the options in the query of a connection string.
+///
+/// This struct is created by [`ConnectionString::options`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Options<'a> {
+    inner: Option<core::str::Split<'a, char>>,
+}
+
+impl<'a> Iterator for Options<'a> {
+    type Item = (&'a EStr<Query>, &'a EStr<Query>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let option = self.inner.as_mut()?.find(|option| !option.is_empty())?;
+        let option = EStr::<Query>::new_validated(option);
+        Some(option.split_once('=').unwrap_or((option, EStr::EMPTY)))
+    }
+}
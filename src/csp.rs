@@ -0,0 +1,348 @@
+//! Content Security Policy source expressions.
+//!
+//! [`SourceExpression`] parses the scheme-source, host-source and `'self'`
+//! expressions of [CSP Level 3] and matches URIs against them as per
+//! [Section 6.7.2]. [`SourceList`] parses a list of such expressions,
+//! as found in a directive value like that of `script-src`.
+//!
+//! [CSP Level 3]: https://www.w3.org/TR/CSP3/#framework-directive-source-list
+//! [Section 6.7.2]: https://www.w3.org/TR/CSP3/#match-url-to-source-expression
+
+use crate::{
+    component::{Host, Scheme},
+    encoding::{encoder::Path, EStr},
+    origin::{default_port, Origin, TupleOrigin},
+    Uri,
+};
+use alloc::vec::Vec;
+use borrow_or_share::Bos;
+
+/// A host-source expression such as `https://*.cdn.example:*/static/`.
+///
+/// This struct is contained in [`SourceExpression::Host`].
+#[derive(Clone, Copy, Debug)]
+pub struct HostSource<'a> {
+    scheme: Option<&'a Scheme>,
+    host: &'a str,
+    port: Option<&'a str>,
+    path: Option<&'a EStr<Path>>,
+}
+
+impl<'a> HostSource<'a> {
+    /// Returns the optional scheme-part.
+    #[must_use]
+    pub fn scheme(&self) -> Option<&'a Scheme> {
+        self.scheme
+    }
+
+    /// Returns the host-part, which is `"*"`, a host, or a host
+    /// prefixed with `"*."`.
+    #[must_use]
+    pub fn host(&self) -> &'a str {
+        self.host
+    }
+
+    /// Returns the optional port-part, which is either digits or `"*"`.
+    #[must_use]
+    pub fn port(&self) -> Option<&'a str> {
+        self.port
+    }
+
+    /// Returns the optional path-part.
+    #[must_use]
+    pub fn path(&self) -> Option<&'a EStr<Path>> {
+        self.path
+    }
+
+    /// Checks whether the expression is exactly `"*"`.
+    fn is_star(&self) -> bool {
+        self.scheme.is_none() && self.host == "*" && self.port.is_none() && self.path.is_none()
+    }
+
+    fn parse(s: &'a str) -> Option<Self> {
+        let (scheme, rest) = match s.split_once("://") {
+            Some((scheme, rest)) => (Some(Scheme::new(scheme)?), rest),
+            None => (None, s),
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], Some(EStr::new(&rest[i..])?)),
+            None => (rest, None),
+        };
+        let (host, port) = match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        };
+
+        if !is_host_part(host) {
+            return None;
+        }
+        if let Some(port) = port {
+            if port != "*" && (port.is_empty() || !port.bytes().all(|x| x.is_ascii_digit())) {
+                return None;
+            }
+        }
+        Some(Self {
+            scheme,
+            host,
+            port,
+            path,
+        })
+    }
+}
+
+/// Checks whether a string matches the `host-part` ABNF rule.
+fn is_host_part(s: &str) -> bool {
+    if s == "*" {
+        return true;
+    }
+    let s = s.strip_prefix("*.").unwrap_or(s);
+    let s = s.strip_suffix('.').unwrap_or(s);
+    !s.is_empty()
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .bytes()
+                    .all(|x| x.is_ascii_alphanumeric() || x == b'-')
+        })
+}
+
+/// A CSP source expression that may match a URI.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{csp::SourceExpression, Uri};
+///
+/// let page = Uri::parse("https://example.com/index.html")?.origin();
+/// let expr = SourceExpression::parse("https://*.cdn.example:*/static/").unwrap();
+///
+/// let matches = |s| expr.matches(&Uri::parse(s).unwrap(), &page);
+/// assert!(matches("https://a.cdn.example/static/app.js"));
+/// assert!(matches("https://a.b.cdn.example:8443/static/js/app.js"));
+/// assert!(!matches("https://cdn.example/static/app.js"));
+/// assert!(!matches("https://a.cdn.example/app.js"));
+/// assert!(!matches("http://a.cdn.example/static/app.js"));
+///
+/// let expr = SourceExpression::parse("'self'").unwrap();
+/// assert!(expr.matches(&Uri::parse("https://example.com/app.js")?, &page));
+/// assert!(!expr.matches(&Uri::parse("https://cdn.example/app.js")?, &page));
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum SourceExpression<'a> {
+    /// A scheme-source such as `https:`.
+    Scheme(&'a Scheme),
+    /// A host-source such as `*`, `example.com`, or `https://*.example.com:443/path`.
+    Host(HostSource<'a>),
+    /// The keyword-source `'self'`.
+    SelfOrigin,
+}
+
+impl<'a> SourceExpression<'a> {
+    /// Parses a scheme-source, a host-source, or the keyword-source `'self'`.
+    ///
+    /// Returns `None` if the string is none of them. In particular, other
+    /// keyword-sources such as `'unsafe-inline'`, nonce-sources and
+    /// hash-sources are not parsed, as they never match a URI.
+    #[must_use]
+    pub fn parse(s: &'a str) -> Option<Self> {
+        if s.eq_ignore_ascii_case("'self'") {
+            return Some(Self::SelfOrigin);
+        }
+        if let Some(scheme) = s.strip_suffix(':') {
+            return Scheme::new(scheme).map(Self::Scheme);
+        }
+        HostSource::parse(s).map(Self::Host)
+    }
+
+    /// Checks whether the URI matches the expression in the context
+    /// of the given origin, i.e., that of the protected resource.
+    ///
+    /// This implements the algorithm for a redirect count of zero. As in major
+    /// browsers, a URI whose host is an IP address matches a host-part that
+    /// is the same address, although the specification does not allow it.
+    #[must_use]
+    pub fn matches<T: Bos<str>>(&self, uri: &Uri<T>, self_origin: &Origin) -> bool {
+        let scheme = uri.scheme().as_str();
+        let self_scheme = match self_origin {
+            Origin::Tuple(origin) => Some(origin.scheme()),
+            Origin::Opaque => None,
+        };
+
+        match self {
+            Self::Scheme(expr) => scheme_part_matches(expr.as_str(), scheme),
+            Self::Host(expr) => {
+                if expr.is_star() {
+                    return is_http_scheme(scheme)
+                        || self_scheme.is_some_and(|s| s.eq_ignore_ascii_case(scheme));
+                }
+                let Origin::Tuple(target) = uri.origin() else {
+                    return false;
+                };
+                let scheme_ok = match expr.scheme {
+                    Some(expr) => scheme_part_matches(expr.as_str(), scheme),
+                    None => self_scheme.is_some_and(|s| scheme_part_matches(s, scheme)),
+                };
+                scheme_ok
+                    && host_part_matches(expr.host, uri, &target)
+                    && port_part_matches(expr.port, &target)
+                    && expr
+                        .path
+                        .is_none_or(|path| path_part_matches(path, uri.path()))
+            }
+            Self::SelfOrigin => {
+                let (Origin::Tuple(origin), Origin::Tuple(url)) = (self_origin, &uri.origin())
+                else {
+                    return false;
+                };
+                if origin == url {
+                    return true;
+                }
+                origin.host() == url.host()
+                    && (origin.port() == url.port() || (is_default(origin) && is_default(url)))
+                    && (matches!(url.scheme(), "https" | "wss")
+                        || (origin.scheme() == "http" && matches!(url.scheme(), "http" | "ws")))
+            }
+        }
+    }
+}
+
+/// Checks whether the port of a tuple origin is the default port of its scheme.
+fn is_default(origin: &TupleOrigin) -> bool {
+    Scheme::new(origin.scheme()).and_then(default_port) == origin.port()
+}
+
+fn is_http_scheme(scheme: &str) -> bool {
+    scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+}
+
+/// Implements [scheme-part matching], allowing upgrades to secure schemes.
+///
+/// [scheme-part matching]: https://www.w3.org/TR/CSP3/#match-schemes
+fn scheme_part_matches(expr: &str, scheme: &str) -> bool {
+    let expr = expr.to_ascii_lowercase();
+    let scheme = scheme.to_ascii_lowercase();
+    expr == scheme
+        || matches!(
+            (&expr[..], &scheme[..]),
+            ("http", "https") | ("ws", "wss" | "http" | "https") | ("wss", "https")
+        )
+}
+
+/// Implements [host-part matching].
+///
+/// [host-part matching]: https://www.w3.org/TR/CSP3/#match-hosts
+fn host_part_matches<T: Bos<str>>(expr: &str, uri: &Uri<T>, target: &TupleOrigin) -> bool {
+    if expr == "*" {
+        return true;
+    }
+    let host = target.host();
+    match expr.strip_prefix('*') {
+        Some(suffix) => {
+            let is_reg_name = uri
+                .authority()
+                .is_some_and(|auth| matches!(auth.host_parsed(), Host::RegName(_)));
+            is_reg_name && host.len() > suffix.len() && ends_with_ignore_case(host, suffix)
+        }
+        None => expr.eq_ignore_ascii_case(host),
+    }
+}
+
+fn ends_with_ignore_case(s: &str, suffix: &str) -> bool {
+    s.len() >= suffix.len()
+        && s.as_bytes()[s.len() - suffix.len()..].eq_ignore_ascii_case(suffix.as_bytes())
+}
+
+/// Implements [port-part matching].
+///
+/// The port of a tuple origin is the default port of its scheme
+/// if the URI has no port or the port is the default one.
+///
+/// [port-part matching]: https://www.w3.org/TR/CSP3/#match-ports
+fn port_part_matches(expr: Option<&str>, target: &TupleOrigin) -> bool {
+    match expr {
+        None => is_default(target),
+        Some("*") => true,
+        Some(port) => port.parse::<u16>().ok() == target.port(),
+    }
+}
+
+/// Implements [path-part matching].
+///
+/// [path-part matching]: https://www.w3.org/TR/CSP3/#match-paths
+fn path_part_matches(expr: &EStr<Path>, path: &EStr<Path>) -> bool {
+    if expr.is_empty() || (expr == "/" && path.is_empty()) {
+        return true;
+    }
+    let exact = !expr.as_str().ends_with('/');
+
+    let mut expr_pieces: Vec<_> = expr.split('/').collect();
+    let path_pieces: Vec<_> = path.split('/').collect();
+    if expr_pieces.len() > path_pieces.len() || (exact && expr_pieces.len() != path_pieces.len()) {
+        return false;
+    }
+    if !exact {
+        expr_pieces.pop();
+    }
+    expr_pieces
+        .iter()
+        .zip(&path_pieces)
+        .all(|(a, b)| a.decode().as_bytes() == b.decode().as_bytes())
+}
+
+/// A list of CSP source expressions, as in the value of a fetch directive.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{csp::SourceList, Uri};
+///
+/// let page = Uri::parse("https://example.com/")?.origin();
+/// let list = SourceList::parse("'self' https://cdn.example data: 'unsafe-inline'");
+/// assert_eq!(list.expressions().len(), 3);
+///
+/// assert!(list.matches(&Uri::parse("https://example.com/app.js")?, &page));
+/// assert!(list.matches(&Uri::parse("https://cdn.example/lib.js")?, &page));
+/// assert!(list.matches(&Uri::parse("data:text/javascript,")?, &page));
+/// assert!(!list.matches(&Uri::parse("https://evil.example/x.js")?, &page));
+///
+/// assert!(!SourceList::parse("'none'").matches(&Uri::parse("https://example.com/")?, &page));
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SourceList<'a> {
+    expressions: Vec<SourceExpression<'a>>,
+}
+
+impl<'a> SourceList<'a> {
+    /// Parses a whitespace-separated list of source expressions.
+    ///
+    /// Expressions that are not recognized by [`SourceExpression::parse`],
+    /// including the keyword-source `'none'`, are ignored.
+    #[must_use]
+    pub fn parse(s: &'a str) -> Self {
+        Self {
+            expressions: s
+                .split_ascii_whitespace()
+                .filter_map(SourceExpression::parse)
+                .collect(),
+        }
+    }
+
+    /// Returns the parsed source expressions.
+    #[must_use]
+    pub fn expressions(&self) -> &[SourceExpression<'a>] {
+        &self.expressions
+    }
+
+    /// Checks whether the URI matches any expression in the list.
+    ///
+    /// See [`SourceExpression::matches`] for details.
+    #[must_use]
+    pub fn matches<T: Bos<str>>(&self, uri: &Uri<T>, self_origin: &Origin) -> bool {
+        self.expressions
+            .iter()
+            .any(|expr| expr.matches(uri, self_origin))
+    }
+}
@@ -0,0 +1,492 @@
+//! DNS hostname validation.
+//!
+//! A registered name in a URI may contain any percent-encoded octet and has
+//! no length limit, whereas a hostname passed to a resolver must follow the
+//! rules of [RFC 1034], [RFC 1123], and [RFC 3696]. Use [`Host::as_dns_name`]
+//! to reject names that a resolver would mishandle.
+//!
+//! [`Host::is_subdomain_of`], [`Host::matches_wildcard`], and [`HostSet`]
+//! match registered names against domains and patterns label by label.
+//!
+//! [RFC 1034]: https://datatracker.ietf.org/doc/html/rfc1034#section-3.5
+//! [RFC 1123]: https://datatracker.ietf.org/doc/html/rfc1123#section-2.1
+//! [RFC 3696]: https://datatracker.ietf.org/doc/html/rfc3696#section-2
+
+use crate::{
+    component::Host,
+    error::{DnsNameError, DnsNameErrorKind},
+};
+use alloc::{borrow::Cow, collections::BTreeSet, string::String};
+
+const MAX_NAME_LEN: usize = 253;
+const MAX_LABEL_LEN: usize = 63;
+
+/// Options for validating a DNS hostname.
+///
+/// This struct is used by [`Host::as_dns_name_with`].
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct DnsNameOptions {
+    allow_underscore: bool,
+}
+
+impl DnsNameOptions {
+    /// Creates a new set of options that only allows letters, digits and hyphens.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether underscores are allowed in labels, as in `_dmarc.example.com`
+    /// or `_http._tcp.example.com`.
+    ///
+    /// Such names are valid DNS names but not valid hostnames.
+    pub fn allow_underscore(self, allow: bool) -> Self {
+        Self {
+            allow_underscore: allow,
+        }
+    }
+}
+
+/// A validated DNS hostname.
+///
+/// This struct is created by [`Host::as_dns_name`].
+/// The name is percent-decoded, consists of ASCII characters only, and is
+/// stored without the trailing dot of a fully qualified name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DnsName<'a> {
+    name: Cow<'a, str>,
+    fqdn: bool,
+}
+
+impl<'a> DnsName<'a> {
+    /// Returns the name as a string slice, without the trailing dot.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// Checks whether the name was written with a trailing dot,
+    /// i.e., as a fully qualified domain name.
+    #[must_use]
+    pub fn is_fqdn(&self) -> bool {
+        self.fqdn
+    }
+
+    /// Returns an iterator over the labels of the name, from left to right.
+    pub fn labels(&self) -> Labels<'_> {
+        Labels {
+            inner: self.name.split('.'),
+        }
+    }
+
+    /// Consumes this `DnsName` and yields the underlying string,
+    /// without the trailing dot.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.name.into_owned()
+    }
+}
+
+/// An iterator over the labels of a DNS hostname.
+///
+/// This struct is created by [`DnsName::labels`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Labels<'a> {
+    inner: core::str::Split<'a, char>,
+}
+
+impl<'a> Iterator for Labels<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl DoubleEndedIterator for Labels<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a> Host<'a> {
+    /// Validates the host as a DNS hostname.
+    ///
+    /// This is equivalent to `as_dns_name_with(DnsNameOptions::new())`.
+    /// See [`as_dns_name_with`] for details.
+    ///
+    /// [`as_dns_name_with`]: Self::as_dns_name_with
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the host is not a valid DNS hostname.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::component::Host;
+    ///
+    /// let name = Host::parse("WWW.Example.com.").unwrap().as_dns_name()?;
+    /// assert_eq!(name.as_str(), "WWW.Example.com");
+    /// assert!(name.is_fqdn());
+    /// assert!(name.labels().eq(["WWW", "Example", "com"]));
+    ///
+    /// let is_err = |s| Host::parse(s).unwrap().as_dns_name().is_err();
+    /// assert!(is_err("a..b"));
+    /// assert!(is_err("%00"));
+    /// assert!(is_err("_x_"));
+    /// assert!(is_err("-a.com"));
+    /// assert!(is_err("1.2.3"));
+    /// assert!(is_err("127.0.0.1"));
+    /// # Ok::<_, fluent_uri::error::DnsNameError>(())
+    /// ```
+    pub fn as_dns_name(&self) -> Result<DnsName<'a>, DnsNameError> {
+        self.as_dns_name_with(DnsNameOptions::new())
+    }
+
+    /// Validates the host as a DNS hostname with the given options.
+    ///
+    /// The host must be a registered name. It is percent-decoded and must
+    /// satisfy the following rules:
+    ///
+    /// - The name is not empty and is at most 253 octets long,
+    ///   excluding an optional trailing dot.
+    /// - Each label is between 1 and 63 octets long.
+    /// - Each label consists of ASCII letters, digits and hyphens, and
+    ///   underscores if allowed by the options.
+    /// - No label starts or ends with a hyphen.
+    /// - The last label is not all-numeric, so that the name cannot be
+    ///   mistaken for an IPv4 address in a non-standard form such as `1.2.3`.
+    ///
+    /// Internationalized names must be converted to ASCII first
+    /// with [`EStr::to_ascii`] under the `idna` feature.
+    ///
+    /// [`EStr::to_ascii`]: crate::encoding::EStr::to_ascii
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the host is not a valid DNS hostname.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{component::Host, dns::DnsNameOptions};
+    ///
+    /// let host = Host::parse("_http._tcp.example.com").unwrap();
+    /// assert!(host.as_dns_name().is_err());
+    ///
+    /// let options = DnsNameOptions::new().allow_underscore(true);
+    /// let name = host.as_dns_name_with(options)?;
+    /// assert_eq!(name.labels().next(), Some("_http"));
+    /// # Ok::<_, fluent_uri::error::DnsNameError>(())
+    /// ```
+    pub fn as_dns_name_with(&self, options: DnsNameOptions) -> Result<DnsName<'a>, DnsNameError> {
+        let Host::RegName(name) = self else {
+            return Err(DnsNameError(DnsNameErrorKind::NotRegName));
+        };
+
+        let decoded = name.decode();
+        let mut bytes = decoded.as_bytes();
+        let fqdn = bytes.last() == Some(&b'.');
+        if fqdn {
+            bytes = &bytes[..bytes.len() - 1];
+        }
+
+        if bytes.is_empty() {
+            return Err(DnsNameError(DnsNameErrorKind::Empty));
+        }
+        if bytes.len() > MAX_NAME_LEN {
+            return Err(DnsNameError(DnsNameErrorKind::NameTooLong));
+        }
+
+        let mut last_label = &bytes[..0];
+        for label in bytes.split(|&x| x == b'.') {
+            validate_label(label, options)?;
+            last_label = label;
+        }
+        if last_label.iter().all(u8::is_ascii_digit) {
+            return Err(DnsNameError(DnsNameErrorKind::NumericTld));
+        }
+
+        // The decoded bytes are ASCII after validation.
+        let mut name = decoded.into_string_lossy();
+        if fqdn {
+            match &mut name {
+                Cow::Borrowed(s) => *s = &s[..s.len() - 1],
+                Cow::Owned(s) => _ = s.pop(),
+            }
+        }
+        Ok(DnsName { name, fqdn })
+    }
+}
+
+impl Host<'_> {
+    /// Checks whether the host is a subdomain of the given domain.
+    ///
+    /// As in [RFC 1034], a domain is a subdomain of itself.
+    ///
+    /// The host must be a registered name. It is percent-decoded and compared
+    /// label by label with the domain, ASCII case-insensitively.
+    /// A trailing dot on either side is ignored. Returns `false` if a label
+    /// on either side is not valid in a DNS hostname, e.g., if it decodes
+    /// to an octet other than an ASCII letter, digit or hyphen.
+    ///
+    /// [RFC 1034]: https://datatracker.ietf.org/doc/html/rfc1034#section-3.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::component::Host;
+    ///
+    /// assert!(Host::parse("api.Example.com.")?.is_subdomain_of("example.com"));
+    /// assert!(Host::parse("example.com")?.is_subdomain_of("example.com"));
+    /// assert!(!Host::parse("badexample.com")?.is_subdomain_of("example.com"));
+    /// assert!(!Host::parse("example.com.evil.net")?.is_subdomain_of("example.com"));
+    /// assert!(!Host::parse("evil.net%00.example.com")?.is_subdomain_of("example.com"));
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn is_subdomain_of(&self, domain: &str) -> bool {
+        let (Ok(name), Ok(domain)) = (normalize_name(self), normalize_domain(domain)) else {
+            return false;
+        };
+        is_subdomain(&name, &domain)
+    }
+
+    /// Checks whether the host matches the given pattern
+    /// as per the rules for TLS certificates in [RFC 6125].
+    ///
+    /// The wildcard character `'*'` may only appear as the complete leftmost
+    /// label of the pattern, and it matches exactly one non-empty label.
+    /// Partial wildcards such as `f*.example.com` never match.
+    /// A pattern without a wildcard matches the same name only.
+    ///
+    /// The host must be a registered name. It is percent-decoded and compared
+    /// label by label with the pattern, ASCII case-insensitively.
+    /// A trailing dot on either side is ignored. As in [`is_subdomain_of`],
+    /// labels other than the wildcard must be valid in a DNS hostname.
+    ///
+    /// [`is_subdomain_of`]: Self::is_subdomain_of
+    ///
+    /// [RFC 6125]: https://datatracker.ietf.org/doc/html/rfc6125#section-6.4.3
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::component::Host;
+    ///
+    /// assert!(Host::parse("www.Example.com")?.matches_wildcard("*.example.com"));
+    /// assert!(!Host::parse("example.com")?.matches_wildcard("*.example.com"));
+    /// assert!(!Host::parse("a.b.example.com")?.matches_wildcard("*.example.com"));
+    /// assert!(!Host::parse("foo.example.com")?.matches_wildcard("f*.example.com"));
+    /// assert!(Host::parse("example.com")?.matches_wildcard("example.com"));
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn matches_wildcard(&self, pattern: &str) -> bool {
+        let Ok(name) = normalize_name(self) else {
+            return false;
+        };
+        match pattern.strip_prefix("*.") {
+            Some(parent) => {
+                normalize_domain(parent).is_ok_and(|parent| matches_wildcard(&name, &parent))
+            }
+            None => normalize_domain(pattern).is_ok_and(|pattern| name == pattern),
+        }
+    }
+}
+
+/// A set of host patterns compiled for fast matching.
+///
+/// A pattern is one of the following, written as a registered name:
+///
+/// - `example.com`, which matches the same name only.
+/// - `*.example.com`, which matches a name with exactly one more label,
+///   as in [`Host::matches_wildcard`].
+/// - `.example.com`, which matches `example.com` and all its subdomains,
+///   as in [`Host::is_subdomain_of`].
+///
+/// Patterns and hosts are percent-decoded and compared label by label,
+/// ASCII case-insensitively, with a trailing dot ignored. Only registered
+/// names whose labels are valid in a DNS hostname are matched. Matching
+/// takes a number of lookups proportional to the number of labels in the
+/// host, regardless of the size of the set.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{component::Host, dns::HostSet};
+///
+/// let set = HostSet::from_patterns(["example.com", "*.cdn.example.net", ".internal.example.org"])?;
+///
+/// let contains = |host| set.contains(Host::parse(host).unwrap());
+/// assert!(contains("EXAMPLE.com."));
+/// assert!(!contains("www.example.com"));
+/// assert!(contains("a.cdn.example.net"));
+/// assert!(!contains("a.b.cdn.example.net"));
+/// assert!(contains("internal.example.org"));
+/// assert!(contains("db.eu.internal.example.org"));
+/// assert!(!contains("example.com.evil.net"));
+/// # Ok::<_, fluent_uri::error::DnsNameError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HostSet {
+    exact: BTreeSet<String>,
+    wildcard: BTreeSet<String>,
+    subtree: BTreeSet<String>,
+}
+
+impl HostSet {
+    /// Creates an empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a set from the given patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if any pattern is invalid. See [`insert`](Self::insert).
+    pub fn from_patterns<'a, I: IntoIterator<Item = &'a str>>(
+        patterns: I,
+    ) -> Result<Self, DnsNameError> {
+        let mut set = Self::new();
+        for pattern in patterns {
+            set.insert(pattern)?;
+        }
+        Ok(set)
+    }
+
+    /// Adds a pattern to the set.
+    ///
+    /// Returns whether the pattern was newly inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the pattern is not a registered name, if it contains
+    /// a wildcard other than a complete leftmost label, or if any other label
+    /// is not valid in a DNS hostname.
+    pub fn insert(&mut self, pattern: &str) -> Result<bool, DnsNameError> {
+        let host = Host::parse(pattern).map_err(|_| DnsNameError(DnsNameErrorKind::InvalidChar))?;
+        let Host::RegName(name) = host else {
+            return Err(DnsNameError(DnsNameErrorKind::NotRegName));
+        };
+        let pattern = name
+            .decode()
+            .into_string()
+            .map_err(|_| DnsNameError(DnsNameErrorKind::InvalidChar))?;
+
+        let (set, pattern) = if let Some(parent) = pattern.strip_prefix("*.") {
+            (&mut self.wildcard, parent)
+        } else if let Some(domain) = pattern.strip_prefix('.') {
+            (&mut self.subtree, domain)
+        } else {
+            (&mut self.exact, &pattern[..])
+        };
+        if pattern.contains('*') {
+            return Err(DnsNameError(DnsNameErrorKind::InvalidWildcard));
+        }
+        Ok(set.insert(normalize_domain(pattern)?))
+    }
+
+    /// Checks whether the host matches any pattern in the set.
+    #[must_use]
+    pub fn contains(&self, host: Host<'_>) -> bool {
+        let Ok(name) = normalize_name(&host) else {
+            return false;
+        };
+        if self.exact.contains(&name) {
+            return true;
+        }
+        if let Some((first, parent)) = name.split_once('.') {
+            if !first.is_empty() && self.wildcard.contains(parent) {
+                return true;
+            }
+        }
+
+        let mut domain = &name[..];
+        loop {
+            if self.subtree.contains(domain) {
+                return true;
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) => domain = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Returns the number of patterns in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.exact.len() + self.wildcard.len() + self.subtree.len()
+    }
+
+    /// Checks whether the set contains no patterns.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Percent-decodes and lowercases a registered name, stripping a trailing dot.
+///
+/// Returns `Err` if the host is not a registered name or does not decode
+/// to a domain accepted by [`normalize_domain`].
+fn normalize_name(host: &Host<'_>) -> Result<String, DnsNameError> {
+    let Host::RegName(name) = host else {
+        return Err(DnsNameError(DnsNameErrorKind::NotRegName));
+    };
+    let name = name
+        .decode()
+        .into_string()
+        .map_err(|_| DnsNameError(DnsNameErrorKind::InvalidChar))?;
+    normalize_domain(&name)
+}
+
+/// Lowercases a domain, stripping a trailing dot.
+///
+/// Returns `Err` if the domain is empty or any of its labels is invalid
+/// in a DNS hostname, so that no octet such as `'\0'` or `'/'` can make
+/// a resolver see a different name than the one being matched.
+fn normalize_domain(domain: &str) -> Result<String, DnsNameError> {
+    let domain = domain.strip_suffix('.').unwrap_or(domain);
+    if domain.is_empty() {
+        return Err(DnsNameError(DnsNameErrorKind::Empty));
+    }
+    for label in domain.split('.') {
+        validate_label(label.as_bytes(), DnsNameOptions::new())?;
+    }
+    Ok(domain.to_ascii_lowercase())
+}
+
+fn is_subdomain(name: &str, domain: &str) -> bool {
+    name.strip_suffix(domain)
+        .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+}
+
+fn matches_wildcard(name: &str, parent: &str) -> bool {
+    name.split_once('.')
+        .is_some_and(|(first, rest)| !first.is_empty() && rest == parent)
+}
+
+fn validate_label(label: &[u8], options: DnsNameOptions) -> Result<(), DnsNameError> {
+    if label.is_empty() {
+        return Err(DnsNameError(DnsNameErrorKind::EmptyLabel));
+    }
+    if label.len() > MAX_LABEL_LEN {
+        return Err(DnsNameError(DnsNameErrorKind::LabelTooLong));
+    }
+    let allowed = |x: &u8| {
+        x.is_ascii_alphanumeric() || *x == b'-' || (*x == b'_' && options.allow_underscore)
+    };
+    if !label.iter().all(allowed) {
+        return Err(DnsNameError(DnsNameErrorKind::InvalidChar));
+    }
+    if label[0] == b'-' || label[label.len() - 1] == b'-' {
+        return Err(DnsNameError(DnsNameErrorKind::HyphenAtLabelEdge));
+    }
+    Ok(())
+}
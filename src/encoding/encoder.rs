@@ -18,6 +18,13 @@ impl Encoder for RegName {
     const TABLE: &'static Table = REG_NAME;
 }
 
+/// An encoder for IPv6 zone identifier.
+pub struct ZoneId(());
+
+impl Encoder for ZoneId {
+    const TABLE: &'static Table = ZONE_ID;
+}
+
 /// An encoder for port.
 pub struct Port(());
 
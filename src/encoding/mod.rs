@@ -16,6 +16,9 @@ use alloc::{
 };
 use core::{cmp::Ordering, hash, iter::FusedIterator, marker::PhantomData, str};
 use encoder::Path;
+
+#[cfg(feature = "idna")]
+use crate::error::{IdnaError, IdnaErrorKind};
 use ref_cast::{ref_cast_custom, RefCastCustom};
 
 /// A table specifying the byte patterns allowed in a string.
@@ -366,6 +369,43 @@ impl<E: Encoder> ToOwned for EStr<E> {
 ///
 /// [path]: https://datatracker.ietf.org/doc/html/rfc3986#section-3.3
 impl EStr<Path> {
+    /// Parses a [path] component from a string slice.
+    ///
+    /// This is equivalent to [`EStr::new`] except that it returns a [`ParseError`]
+    /// indicating where the string goes wrong. Use [`EStr::new`] to validate
+    /// a query or fragment component.
+    ///
+    /// Note that the path of a URI (reference) is subject to further constraints
+    /// depending on the presence of other components. See [`Builder::build`] for details.
+    ///
+    /// [path]: https://datatracker.ietf.org/doc/html/rfc3986#section-3.3
+    /// [`ParseError`]: crate::error::ParseError
+    /// [`Builder::build`]: crate::Builder::build
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string does not match the `path` ABNF rule,
+    /// i.e., `*( pchar / "/" )`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::encoding::{encoder::Path, EStr};
+    ///
+    /// let path = EStr::<Path>::parse_path("/over/there")?;
+    /// assert!(path.segments().unwrap().eq(["over", "there"]));
+    ///
+    /// let e = EStr::<Path>::parse_path("/over%2/there").unwrap_err();
+    /// assert_eq!(e.to_string(), "invalid percent-encoded octet at index 5");
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub const fn parse_path(s: &str) -> Result<&Self, crate::error::ParseError> {
+        match crate::parser::parse_with_table(s.as_bytes(), Path::TABLE) {
+            Ok(()) => Ok(EStr::new_validated(s)),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Checks whether the path is absolute, i.e., starting with `'/'`.
     #[inline]
     #[must_use]
@@ -422,6 +462,85 @@ impl EStr<Path> {
     }
 }
 
+#[cfg(feature = "idna")]
+impl EStr<encoder::RegName> {
+    /// Converts the registered name to an ASCII-compatible domain name
+    /// with [UTS #46] processing.
+    ///
+    /// The registered name is percent-decoded before processing. Non-ASCII labels
+    /// are converted to A-labels (`xn--` labels) and the output is lowercase.
+    /// Any ASCII character in the output that is not allowed in a registered name
+    /// is percent-encoded.
+    ///
+    /// [UTS #46]: https://www.unicode.org/reports/tr46/
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the decoded bytes are not valid UTF-8
+    /// or if UTS #46 processing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{component::Host, encoding::{encoder::RegName, EStr}, Uri};
+    ///
+    /// let uri = Uri::parse("http://%E4%BE%8B%E3%81%88.JP/")?;
+    /// let Host::RegName(name) = uri.authority().unwrap().host_parsed() else { unreachable!() };
+    /// assert_eq!(name.to_ascii().unwrap(), "xn--r8jz45g.jp");
+    ///
+    /// assert!(EStr::<RegName>::new_or_panic("xn--a.com").to_ascii().is_err());
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub fn to_ascii(&self) -> Result<EString<encoder::RegName>, IdnaError> {
+        let decoded = self
+            .decode()
+            .into_string()
+            .map_err(|_| IdnaError(IdnaErrorKind::InvalidUtf8))?;
+        let ascii = idna::domain_to_ascii(&decoded).map_err(|_| IdnaError(IdnaErrorKind::Uts46))?;
+
+        let mut buf = EString::new();
+        buf.encode::<encoder::RegName>(&ascii);
+        Ok(buf)
+    }
+
+    /// Converts the registered name to a Unicode domain name
+    /// with [UTS #46] processing.
+    ///
+    /// The registered name is percent-decoded before processing. A-labels
+    /// (`xn--` labels) are converted to Unicode and the output is lowercase.
+    ///
+    /// The output is not percent-encoded and is intended for display.
+    ///
+    /// [UTS #46]: https://www.unicode.org/reports/tr46/
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the decoded bytes are not valid UTF-8
+    /// or if UTS #46 processing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::encoding::{encoder::RegName, EStr};
+    ///
+    /// let name = EStr::<RegName>::new_or_panic("xn--r8jz45g.JP");
+    /// assert_eq!(name.to_unicode().unwrap(), "例え.jp");
+    ///
+    /// let name = EStr::<RegName>::new_or_panic("%E4%BE%8B%E3%81%88.jp");
+    /// assert_eq!(name.to_unicode().unwrap(), "例え.jp");
+    /// ```
+    pub fn to_unicode(&self) -> Result<String, IdnaError> {
+        let decoded = self
+            .decode()
+            .into_string()
+            .map_err(|_| IdnaError(IdnaErrorKind::InvalidUtf8))?;
+        match idna::domain_to_unicode(&decoded) {
+            (unicode, Ok(())) => Ok(unicode),
+            (_, Err(_)) => Err(IdnaError(IdnaErrorKind::Uts46)),
+        }
+    }
+}
+
 /// A wrapper of percent-decoded bytes.
 ///
 /// This enum is created by [`EStr::decode`].
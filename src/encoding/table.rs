@@ -189,6 +189,9 @@ pub const USERINFO: &Table = &UNRESERVED.or(SUB_DELIMS).or(&gen(b":")).enc();
 /// `IPvFuture = "v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )`
 pub const IPV_FUTURE: &Table = &UNRESERVED.or(SUB_DELIMS).or(&gen(b":"));
 
+/// `ZoneID = 1*( unreserved / pct-encoded )`
+pub const ZONE_ID: &Table = &UNRESERVED.enc();
+
 /// `reg-name = *( unreserved / pct-encoded / sub-delims )`
 pub const REG_NAME: &Table = &UNRESERVED.or(SUB_DELIMS).enc();
 
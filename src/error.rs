@@ -19,6 +19,16 @@ pub(crate) enum ParseErrorKind {
     InvalidIpv6Addr,
 }
 
+impl ParseErrorKind {
+    pub(crate) const fn description(self) -> &'static str {
+        match self {
+            Self::InvalidOctet => "invalid percent-encoded octet",
+            Self::UnexpectedChar => "unexpected character",
+            Self::InvalidIpv6Addr => "invalid IPv6 address",
+        }
+    }
+}
+
 /// An error occurred when parsing a URI (reference).
 #[derive(Clone, Copy)]
 pub struct ParseError<I = NoInput> {
@@ -64,6 +74,7 @@ pub(crate) enum BuildErrorKind {
     NonAbemptyPath,
     PathStartingWithDoubleSlash,
     ColonInFirstPathSegment,
+    EmptyZoneId,
 }
 
 /// An error occurred when building a URI (reference).
@@ -87,3 +98,209 @@ pub struct ResolveError(pub(crate) ResolveErrorKind);
 
 #[cfg(feature = "std")]
 impl std::error::Error for ResolveError {}
+
+/// Detailed cause of an [`EffectiveUriError`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum EffectiveUriErrorKind {
+    MissingHostHeader,
+    InvalidHostHeader,
+    EmptyHost,
+}
+
+/// An error occurred when reconstructing the target URI of an HTTP request.
+#[derive(Clone, Copy, Debug)]
+pub struct EffectiveUriError(pub(crate) EffectiveUriErrorKind);
+
+#[cfg(feature = "std")]
+impl std::error::Error for EffectiveUriError {}
+
+/// Detailed cause of an [`HttpUriError`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum HttpUriErrorKind {
+    InvalidUri,
+    NotHttp,
+    EmptyHost,
+    Userinfo,
+    InvalidPort,
+}
+
+/// An error occurred when validating an HTTP(S) URI.
+#[derive(Clone, Copy, Debug)]
+pub struct HttpUriError(pub(crate) HttpUriErrorKind);
+
+#[cfg(feature = "std")]
+impl std::error::Error for HttpUriError {}
+
+/// Detailed cause of an [`IdnaError`].
+#[cfg(feature = "idna")]
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum IdnaErrorKind {
+    InvalidUtf8,
+    Uts46,
+}
+
+/// An error occurred when processing an internationalized domain name.
+#[cfg(feature = "idna")]
+#[derive(Clone, Copy, Debug)]
+pub struct IdnaError(pub(crate) IdnaErrorKind);
+
+#[cfg(all(feature = "idna", feature = "std"))]
+impl std::error::Error for IdnaError {}
+
+/// Detailed cause of a [`PolicyError`].
+#[cfg(feature = "net")]
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum PolicyErrorKind {
+    NoHost,
+    DeniedHost,
+    DisallowedClass(crate::policy::HostClass),
+}
+
+/// An error occurred when checking a URI against a destination policy.
+#[cfg(feature = "net")]
+#[derive(Clone, Copy, Debug)]
+pub struct PolicyError(pub(crate) PolicyErrorKind);
+
+#[cfg(feature = "net")]
+impl PolicyError {
+    /// Returns the class of the host if the error is caused by a disallowed class.
+    #[must_use]
+    pub fn host_class(&self) -> Option<crate::policy::HostClass> {
+        match self.0 {
+            PolicyErrorKind::DisallowedClass(class) => Some(class),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(feature = "net", feature = "std"))]
+impl std::error::Error for PolicyError {}
+
+/// Detailed cause of a [`DnsNameError`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum DnsNameErrorKind {
+    NotRegName,
+    Empty,
+    NameTooLong,
+    EmptyLabel,
+    LabelTooLong,
+    InvalidChar,
+    HyphenAtLabelEdge,
+    NumericTld,
+    InvalidWildcard,
+}
+
+/// An error occurred when validating a host as a DNS hostname.
+#[derive(Clone, Copy, Debug)]
+pub struct DnsNameError(pub(crate) DnsNameErrorKind);
+
+#[cfg(feature = "std")]
+impl std::error::Error for DnsNameError {}
+
+/// Detailed cause of a [`RedirectUriError`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum RedirectUriErrorKind {
+    Fragment,
+    Userinfo,
+    Wildcard,
+    Mismatch,
+}
+
+/// An error occurred when validating or comparing OAuth 2.0 redirect URIs.
+#[derive(Clone, Copy, Debug)]
+pub struct RedirectUriError(pub(crate) RedirectUriErrorKind);
+
+#[cfg(feature = "std")]
+impl std::error::Error for RedirectUriError {}
+
+/// Detailed cause of a [`SpiffeIdError`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum SpiffeIdErrorKind {
+    InvalidUri,
+    TooLong,
+    InvalidScheme,
+    EmptyTrustDomain,
+    InvalidTrustDomain,
+    Userinfo,
+    Port,
+    Query,
+    Fragment,
+    EmptySegment,
+    DotSegment,
+    InvalidPathChar,
+}
+
+/// An error occurred when validating a SPIFFE ID.
+#[derive(Clone, Copy, Debug)]
+pub struct SpiffeIdError(pub(crate) SpiffeIdErrorKind);
+
+#[cfg(feature = "std")]
+impl std::error::Error for SpiffeIdError {}
+
+/// Detailed cause of a [`SchemeSyntaxError`].
+#[derive(Debug)]
+pub(crate) enum SchemeSyntaxErrorKind {
+    Parse(ParseError),
+    Build(BuildError),
+    UnexpectedScheme,
+    Http(HttpUriErrorKind),
+    FileUserinfo,
+    FilePort,
+    FileNotAbsolute,
+    FileQuery,
+    Invalid,
+}
+
+/// An error occurred when validating a URI against a scheme-specific syntax.
+#[derive(Debug)]
+pub struct SchemeSyntaxError(pub(crate) SchemeSyntaxErrorKind);
+
+impl SchemeSyntaxError {
+    /// Creates an error for a URI that violates a rule of
+    /// a third-party [`SchemeSyntax`].
+    ///
+    /// [`SchemeSyntax`]: crate::syntax::SchemeSyntax
+    #[must_use]
+    pub const fn invalid() -> Self {
+        Self(SchemeSyntaxErrorKind::Invalid)
+    }
+}
+
+impl From<ParseError> for SchemeSyntaxError {
+    fn from(e: ParseError) -> Self {
+        Self(SchemeSyntaxErrorKind::Parse(e))
+    }
+}
+
+impl From<BuildError> for SchemeSyntaxError {
+    fn from(e: BuildError) -> Self {
+        Self(SchemeSyntaxErrorKind::Build(e))
+    }
+}
+
+impl From<HttpUriError> for SchemeSyntaxError {
+    fn from(e: HttpUriError) -> Self {
+        Self(SchemeSyntaxErrorKind::Http(e.0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SchemeSyntaxError {}
+
+/// Detailed cause of a [`FilePathError`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum FilePathErrorKind {
+    NotFileScheme,
+    UserinfoOrPort,
+    NonLocalHost,
+    NotAbsolute,
+    InvalidUncPath,
+    InvalidUtf8,
+}
+
+/// An error occurred when converting between a `file` URI and a filesystem path.
+#[derive(Clone, Copy, Debug)]
+pub struct FilePathError(pub(crate) FilePathErrorKind);
+
+#[cfg(feature = "std")]
+impl std::error::Error for FilePathError {}
@@ -0,0 +1,396 @@
+//! Conversion between `file` URIs and filesystem paths as per RFC 8089.
+
+use crate::{
+    component::Scheme,
+    encoding::{
+        encoder::{Path, RegName},
+        EStr, EString,
+    },
+    error::{FilePathError, FilePathErrorKind},
+    Uri,
+};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use borrow_or_share::Bos;
+
+const SCHEME_FILE: &Scheme = Scheme::new_or_panic("file");
+
+impl Uri<String> {
+    /// Creates a `file` URI from an absolute Unix path given as raw bytes.
+    ///
+    /// Bytes outside the path character set, including `'%'`, `'?'`, `'#'`,
+    /// space and non-ASCII bytes, are percent-encoded, so that
+    /// [`to_unix_path`] returns the exact same bytes. Dot segments
+    /// are kept as they are.
+    ///
+    /// [`to_unix_path`]: Self::to_unix_path
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the path does not start with `'/'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::Uri;
+    ///
+    /// let uri = Uri::from_unix_path(b"/home/user/a b#1.rs")?;
+    /// assert_eq!(uri, "file:///home/user/a%20b%231.rs");
+    ///
+    /// let uri = Uri::from_unix_path(b"/tmp/\xFF")?;
+    /// assert_eq!(uri, "file:///tmp/%FF");
+    ///
+    /// assert!(Uri::from_unix_path(b"src/lib.rs").is_err());
+    /// # Ok::<_, fluent_uri::error::FilePathError>(())
+    /// ```
+    pub fn from_unix_path(path: &[u8]) -> Result<Self, FilePathError> {
+        if path.first() != Some(&b'/') {
+            return Err(FilePathError(FilePathErrorKind::NotAbsolute));
+        }
+        let mut buf = EString::<Path>::new();
+        buf.encode::<Path>(path);
+        Ok(build(EStr::new_or_panic(""), &buf))
+    }
+
+    /// Creates a `file` URI from an absolute Windows path.
+    ///
+    /// This method works on any platform. Both `'\\'` and `'/'` are accepted
+    /// as separators. The following forms are supported:
+    ///
+    /// - Drive paths, as in `C:\dir\file.txt` or `C:`,
+    ///   converted to `file:///C:/dir/file.txt` or `file:///C:/`.
+    /// - UNC paths, as in `\\server\share\file.txt`,
+    ///   converted to `file://server/share/file.txt`.
+    /// - Verbatim paths, as in `\\?\C:\dir` or `\\?\UNC\server\share`,
+    ///   converted as if the `\\?\` or `\\?\UNC` prefix were absent.
+    ///
+    /// Characters outside the path character set are percent-encoded
+    /// as UTF-8, and characters outside the registered name character set
+    /// in the server name are percent-encoded likewise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the path is relative, drive-relative (as in `C:dir`),
+    /// or a UNC path without a server or share name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::Uri;
+    ///
+    /// let uri = Uri::from_windows_path(r"C:\Users\me\My Documents\#1.txt")?;
+    /// assert_eq!(uri, "file:///C:/Users/me/My%20Documents/%231.txt");
+    ///
+    /// let uri = Uri::from_windows_path(r"\\server\share\a.txt")?;
+    /// assert_eq!(uri, "file://server/share/a.txt");
+    ///
+    /// assert!(Uri::from_windows_path(r"C:dir").is_err());
+    /// assert!(Uri::from_windows_path(r"\dir").is_err());
+    /// # Ok::<_, fluent_uri::error::FilePathError>(())
+    /// ```
+    pub fn from_windows_path(path: &str) -> Result<Self, FilePathError> {
+        let (unc, path) = if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+            (true, rest)
+        } else if let Some(rest) = path.strip_prefix(r"\\?\") {
+            (false, rest)
+        } else if let Some(rest) = strip_two_separators(path) {
+            (true, rest)
+        } else {
+            (false, path)
+        };
+
+        let mut buf = EString::<Path>::new();
+        if unc {
+            let (server, rest) = path.split_once(is_separator).unwrap_or((path, ""));
+            let share = rest.split(is_separator).next().unwrap_or("");
+            if server.is_empty() || share.is_empty() {
+                return Err(FilePathError(FilePathErrorKind::InvalidUncPath));
+            }
+
+            let mut host = EString::<RegName>::new();
+            host.encode::<RegName>(server);
+            buf.push_byte(b'/');
+            encode_windows(&mut buf, rest);
+            return Ok(build(&host, &buf));
+        }
+
+        let bytes = path.as_bytes();
+        if bytes.len() < 2
+            || !bytes[0].is_ascii_alphabetic()
+            || bytes[1] != b':'
+            || bytes.get(2).is_some_and(|&x| !is_separator(x as char))
+        {
+            return Err(FilePathError(FilePathErrorKind::NotAbsolute));
+        }
+
+        buf.push_byte(b'/');
+        buf.push_estr(EStr::new_or_panic(&path[..2]));
+        buf.push_byte(b'/');
+        encode_windows(&mut buf, path.get(3..).unwrap_or(""));
+        Ok(build(EStr::new_or_panic(""), &buf))
+    }
+}
+
+impl<T: Bos<str>> Uri<T> {
+    /// Converts a `file` URI to an absolute Unix path given as raw bytes.
+    ///
+    /// The host must be empty, absent, or `localhost` (case-insensitive).
+    /// The path is percent-decoded into arbitrary bytes. The query and
+    /// fragment are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the scheme is not `file`, the authority has a userinfo,
+    /// port, or non-local host, or the path does not start with `'/'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::Uri;
+    ///
+    /// let uri = Uri::parse("file:///home/user/a%20b%231.rs")?;
+    /// assert_eq!(uri.to_unix_path().unwrap(), b"/home/user/a b#1.rs");
+    ///
+    /// let uri = Uri::parse("file://localhost/etc/hosts")?;
+    /// assert_eq!(uri.to_unix_path().unwrap(), b"/etc/hosts");
+    ///
+    /// assert!(Uri::parse("file://server/etc/hosts")?.to_unix_path().is_err());
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub fn to_unix_path(&self) -> Result<Vec<u8>, FilePathError> {
+        if check_file_uri(self)?.is_some() {
+            return Err(FilePathError(FilePathErrorKind::NonLocalHost));
+        }
+        if !self.path().is_absolute() {
+            return Err(FilePathError(FilePathErrorKind::NotAbsolute));
+        }
+        Ok(self.path().decode().into_bytes().into_owned())
+    }
+
+    /// Converts a `file` URI to an absolute Windows path.
+    ///
+    /// This method works on any platform. The following forms are supported:
+    ///
+    /// - An empty, absent, or `localhost` host with a path that starts
+    ///   with a drive letter, as in `file:///C:/dir` or `file:///c%7C/dir`,
+    ///   converted to `C:\dir` or `c:\dir`.
+    /// - A non-local host, as in `file://server/share/dir`,
+    ///   converted to the UNC path `\\server\share\dir`.
+    /// - An empty host with a UNC path, as in `file:////server/share/dir`,
+    ///   converted likewise.
+    ///
+    /// The host and path are percent-decoded as UTF-8, and `'/'` is replaced
+    /// with `'\\'`. The query and fragment are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the scheme is not `file`, the authority has a userinfo
+    /// or port, the host or path is not valid UTF-8 after decoding, the host
+    /// is an IP literal, or the path is neither a drive path nor a UNC path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::Uri;
+    ///
+    /// let uri = Uri::parse("file:///C:/Users/me/My%20Documents/%231.txt")?;
+    /// assert_eq!(uri.to_windows_path().unwrap(), r"C:\Users\me\My Documents\#1.txt");
+    ///
+    /// let uri = Uri::parse("file://server/share/a.txt")?;
+    /// assert_eq!(uri.to_windows_path().unwrap(), r"\\server\share\a.txt");
+    ///
+    /// assert!(Uri::parse("file:///etc/hosts")?.to_windows_path().is_err());
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub fn to_windows_path(&self) -> Result<String, FilePathError> {
+        let host = check_file_uri(self)?;
+        let path = self
+            .path()
+            .decode()
+            .into_string()
+            .map_err(|_| FilePathError(FilePathErrorKind::InvalidUtf8))?;
+
+        let mut buf = String::new();
+        let rest = if let Some(host) = host {
+            if host.starts_with('[') {
+                return Err(FilePathError(FilePathErrorKind::InvalidUncPath));
+            }
+            let host = EStr::<RegName>::new_or_panic(host)
+                .decode()
+                .into_string()
+                .map_err(|_| FilePathError(FilePathErrorKind::InvalidUtf8))?;
+            push_unc(&mut buf, &host, &path)?
+        } else if let Some(unc) = path.strip_prefix("//") {
+            let (server, rest) = unc.split_once('/').unwrap_or((unc, ""));
+            push_unc(&mut buf, server, rest)?
+        } else {
+            let bytes = path.as_bytes();
+            if bytes.len() < 3
+                || bytes[0] != b'/'
+                || !bytes[1].is_ascii_alphabetic()
+                || !matches!(bytes[2], b':' | b'|')
+                || bytes.get(3).is_some_and(|&x| x != b'/')
+            {
+                return Err(FilePathError(FilePathErrorKind::NotAbsolute));
+            }
+            buf.push(bytes[1] as char);
+            buf.push(':');
+            match &path[3..] {
+                "" => "/",
+                rest => rest,
+            }
+        };
+
+        buf.extend(rest.chars().map(|c| if c == '/' { '\\' } else { c }));
+        Ok(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Uri<String> {
+    /// Creates a `file` URI from an absolute filesystem path.
+    ///
+    /// On Unix, this is equivalent to [`from_unix_path`] with the raw bytes
+    /// of the path, so that any path is converted losslessly. On Windows,
+    /// this is equivalent to [`from_windows_path`]. On other platforms,
+    /// the path is treated as a Unix path and must be valid UTF-8.
+    ///
+    /// [`from_unix_path`]: Self::from_unix_path
+    /// [`from_windows_path`]: Self::from_windows_path
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the path is not absolute, or is not valid UTF-8
+    /// on platforms other than Unix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use fluent_uri::Uri;
+    ///
+    /// let uri = Uri::from_file_path("/home/user/a b.rs")?;
+    /// assert_eq!(uri, "file:///home/user/a%20b.rs");
+    /// assert_eq!(uri.to_file_path()?, std::path::Path::new("/home/user/a b.rs"));
+    /// # }
+    /// # Ok::<_, fluent_uri::error::FilePathError>(())
+    /// ```
+    pub fn from_file_path(path: impl AsRef<std::path::Path>) -> Result<Self, FilePathError> {
+        let path = path.as_ref().as_os_str();
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Self::from_unix_path(path.as_bytes())
+        }
+        #[cfg(windows)]
+        {
+            Self::from_windows_path(os_str_to_str(path)?)
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            Self::from_unix_path(os_str_to_str(path)?.as_bytes())
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Bos<str>> Uri<T> {
+    /// Converts a `file` URI to an absolute filesystem path.
+    ///
+    /// On Unix, this is equivalent to [`to_unix_path`], so that any byte
+    /// sequence is preserved. On Windows, this is equivalent to [`to_windows_path`].
+    /// On other platforms, the URI is treated as for a Unix path and the path
+    /// must be valid UTF-8 after decoding.
+    ///
+    /// [`to_unix_path`]: Self::to_unix_path
+    /// [`to_windows_path`]: Self::to_windows_path
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the conversion fails on the current platform.
+    pub fn to_file_path(&self) -> Result<std::path::PathBuf, FilePathError> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStringExt;
+            Ok(std::ffi::OsString::from_vec(self.to_unix_path()?).into())
+        }
+        #[cfg(windows)]
+        {
+            self.to_windows_path().map(Into::into)
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            String::from_utf8(self.to_unix_path()?)
+                .map(Into::into)
+                .map_err(|_| FilePathError(FilePathErrorKind::InvalidUtf8))
+        }
+    }
+}
+
+/// Checks the scheme and authority of a `file` URI,
+/// returning the host if it is not local.
+fn check_file_uri<T: Bos<str>>(uri: &Uri<T>) -> Result<Option<&str>, FilePathError> {
+    if uri.scheme() != SCHEME_FILE {
+        return Err(FilePathError(FilePathErrorKind::NotFileScheme));
+    }
+    let Some(auth) = uri.authority() else {
+        return Ok(None);
+    };
+    if auth.has_userinfo() || auth.has_port() {
+        return Err(FilePathError(FilePathErrorKind::UserinfoOrPort));
+    }
+    let host = auth.host();
+    if host.is_empty() || host.eq_ignore_ascii_case("localhost") {
+        Ok(None)
+    } else {
+        Ok(Some(host))
+    }
+}
+
+/// Pushes the server name of a UNC path, returning the share and the rest.
+fn push_unc<'a>(buf: &mut String, server: &str, rest: &'a str) -> Result<&'a str, FilePathError> {
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    if server.is_empty() || rest.split('/').next().unwrap_or("").is_empty() {
+        return Err(FilePathError(FilePathErrorKind::InvalidUncPath));
+    }
+    buf.push_str(r"\\");
+    buf.push_str(server);
+    buf.push('\\');
+    Ok(rest)
+}
+
+#[cfg(all(feature = "std", not(unix)))]
+fn os_str_to_str(s: &std::ffi::OsStr) -> Result<&str, FilePathError> {
+    s.to_str()
+        .ok_or(FilePathError(FilePathErrorKind::InvalidUtf8))
+}
+
+fn build(host: &EStr<RegName>, path: &EStr<Path>) -> Uri<String> {
+    Uri::builder()
+        .scheme(SCHEME_FILE)
+        .authority_with(|b| b.host(host))
+        .path(path)
+        .build()
+        .unwrap()
+}
+
+fn is_separator(c: char) -> bool {
+    c == '\\' || c == '/'
+}
+
+fn strip_two_separators(s: &str) -> Option<&str> {
+    let mut chars = s.chars();
+    if chars.next().is_some_and(is_separator) && chars.next().is_some_and(is_separator) {
+        Some(chars.as_str())
+    } else {
+        None
+    }
+}
+
+fn encode_windows(buf: &mut EString<Path>, s: &str) {
+    let s: Cow<'_, str> = if s.contains('\\') {
+        s.replace('\\', "/").into()
+    } else {
+        s.into()
+    };
+    buf.encode::<Path>(s.as_bytes());
+}
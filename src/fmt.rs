@@ -1,12 +1,27 @@
 use crate::{
-    component::{Authority, Scheme},
+    component::{Authority, IpvFutureRegistry, Scheme},
     encoding::{EStr, EString, Encoder},
     error::{
-        BuildError, BuildErrorKind, ParseError, ParseErrorKind, ResolveError, ResolveErrorKind,
+        BuildError, BuildErrorKind, DnsNameError, DnsNameErrorKind, EffectiveUriError,
+        EffectiveUriErrorKind, FilePathError, FilePathErrorKind, HttpUriError, HttpUriErrorKind,
+        ParseError, RedirectUriError, RedirectUriErrorKind, ResolveError, ResolveErrorKind,
+        SchemeSyntaxError, SchemeSyntaxErrorKind, SpiffeIdError, SpiffeIdErrorKind,
     },
+    http::HttpUri,
+    origin::{default_port, Origin},
+    redact::Redacted,
+    spiffe::SpiffeId,
+    syntax::SchemeRegistry,
 };
+use alloc::string::ToString;
+use borrow_or_share::Bos;
 use core::fmt::{Debug, Display, Formatter, Result};
 
+#[cfg(feature = "idna")]
+use crate::error::{IdnaError, IdnaErrorKind};
+#[cfg(feature = "net")]
+use crate::error::{PolicyError, PolicyErrorKind};
+
 impl<E: Encoder> Debug for EStr<E> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         Debug::fmt(self.as_str(), f)
@@ -42,12 +57,7 @@ impl<I> Debug for ParseError<I> {
 
 impl<I> Display for ParseError<I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let msg = match self.kind {
-            ParseErrorKind::InvalidOctet => "invalid percent-encoded octet at index ",
-            ParseErrorKind::UnexpectedChar => "unexpected character at index ",
-            ParseErrorKind::InvalidIpv6Addr => "invalid IPv6 address at index ",
-        };
-        write!(f, "{}{}", msg, self.index)
+        write!(f, "{} at index {}", self.kind.description(), self.index)
     }
 }
 
@@ -63,6 +73,7 @@ impl Display for BuildError {
             BuildErrorKind::ColonInFirstPathSegment => {
                 "first path segment cannot contain ':' in relative-path reference"
             }
+            BuildErrorKind::EmptyZoneId => "empty zone identifier in IPv6 address",
         };
         f.write_str(msg)
     }
@@ -80,6 +91,237 @@ impl Display for ResolveError {
     }
 }
 
+impl Display for EffectiveUriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self.0 {
+            EffectiveUriErrorKind::MissingHostHeader => "missing Host header field",
+            EffectiveUriErrorKind::InvalidHostHeader => "invalid Host header field value",
+            EffectiveUriErrorKind::EmptyHost => "empty host in target URI",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Display for DnsNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self.0 {
+            DnsNameErrorKind::NotRegName => "host is not a registered name",
+            DnsNameErrorKind::Empty => "empty DNS name",
+            DnsNameErrorKind::NameTooLong => "DNS name longer than 253 octets",
+            DnsNameErrorKind::EmptyLabel => "empty label in DNS name",
+            DnsNameErrorKind::LabelTooLong => "label longer than 63 octets in DNS name",
+            DnsNameErrorKind::InvalidChar => "invalid character in DNS name",
+            DnsNameErrorKind::HyphenAtLabelEdge => "label starting or ending with '-' in DNS name",
+            DnsNameErrorKind::NumericTld => "all-numeric last label in DNS name",
+            DnsNameErrorKind::InvalidWildcard => "invalid wildcard in host pattern",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Display for HttpUriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self.0 {
+            HttpUriErrorKind::InvalidUri => "invalid URI",
+            HttpUriErrorKind::NotHttp => "scheme other than \"http\" or \"https\"",
+            HttpUriErrorKind::EmptyHost => "missing or empty host in HTTP(S) URI",
+            HttpUriErrorKind::Userinfo => "HTTP(S) URI with userinfo",
+            HttpUriErrorKind::InvalidPort => "invalid port in HTTP(S) URI",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Display for SchemeSyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match &self.0 {
+            SchemeSyntaxErrorKind::Parse(e) => Display::fmt(e, f),
+            SchemeSyntaxErrorKind::Build(e) => Display::fmt(e, f),
+            SchemeSyntaxErrorKind::UnexpectedScheme => {
+                f.write_str("scheme not supported by syntax")
+            }
+            SchemeSyntaxErrorKind::Http(kind) => Display::fmt(&HttpUriError(*kind), f),
+            SchemeSyntaxErrorKind::FileUserinfo => f.write_str("file URI with userinfo"),
+            SchemeSyntaxErrorKind::FilePort => f.write_str("file URI with port"),
+            SchemeSyntaxErrorKind::FileNotAbsolute => {
+                f.write_str("file URI with relative or empty path")
+            }
+            SchemeSyntaxErrorKind::FileQuery => f.write_str("file URI with query"),
+            SchemeSyntaxErrorKind::Invalid => f.write_str("URI violates scheme-specific syntax"),
+        }
+    }
+}
+
+impl Display for FilePathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self.0 {
+            FilePathErrorKind::NotFileScheme => "scheme other than \"file\"",
+            FilePathErrorKind::UserinfoOrPort => "file URI with userinfo or port",
+            FilePathErrorKind::NonLocalHost => "file URI with non-local host",
+            FilePathErrorKind::NotAbsolute => "file path is not absolute",
+            FilePathErrorKind::InvalidUncPath => "UNC path without server or share name",
+            FilePathErrorKind::InvalidUtf8 => "invalid UTF-8 in file path",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Display for RedirectUriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self.0 {
+            RedirectUriErrorKind::Fragment => "redirect URI with fragment",
+            RedirectUriErrorKind::Userinfo => "redirect URI with userinfo",
+            RedirectUriErrorKind::Wildcard => "redirect URI with wildcard",
+            RedirectUriErrorKind::Mismatch => "redirect URI does not match",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Display for SpiffeIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self.0 {
+            SpiffeIdErrorKind::InvalidUri => "invalid URI",
+            SpiffeIdErrorKind::TooLong => "SPIFFE ID longer than 2048 octets",
+            SpiffeIdErrorKind::InvalidScheme => "scheme other than \"spiffe\"",
+            SpiffeIdErrorKind::EmptyTrustDomain => "empty trust domain",
+            SpiffeIdErrorKind::InvalidTrustDomain => "invalid trust domain",
+            SpiffeIdErrorKind::Userinfo => "SPIFFE ID with userinfo",
+            SpiffeIdErrorKind::Port => "SPIFFE ID with port",
+            SpiffeIdErrorKind::Query => "SPIFFE ID with query",
+            SpiffeIdErrorKind::Fragment => "SPIFFE ID with fragment",
+            SpiffeIdErrorKind::EmptySegment => "empty path segment in SPIFFE ID",
+            SpiffeIdErrorKind::DotSegment => "dot segment in SPIFFE ID",
+            SpiffeIdErrorKind::InvalidPathChar => "invalid character in SPIFFE ID path",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "idna")]
+impl Display for IdnaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self.0 {
+            IdnaErrorKind::InvalidUtf8 => "invalid UTF-8 in registered name",
+            IdnaErrorKind::Uts46 => "invalid internationalized domain name",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "net")]
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            PolicyErrorKind::NoHost => f.write_str("destination has no host"),
+            PolicyErrorKind::DeniedHost => f.write_str("destination host is denied"),
+            PolicyErrorKind::DisallowedClass(class) => {
+                write!(
+                    f,
+                    "destination host is {} and not allowed",
+                    class.description()
+                )
+            }
+        }
+    }
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let Origin::Tuple(tuple) = self else {
+            return f.write_str("null");
+        };
+        write!(f, "{}://{}", tuple.scheme(), tuple.host())?;
+        match tuple.port() {
+            Some(port) if Scheme::new(tuple.scheme()).and_then(default_port) != Some(port) => {
+                write!(f, ":{port}")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for Redacted<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let s = self.uri.as_str();
+        let spans = self.uri.spans();
+        let mut i = 0;
+
+        if let (Some(userinfo), Some(username)) = (
+            spans.userinfo,
+            self.uri.authority().and_then(|auth| auth.username()),
+        ) {
+            let start = userinfo.start + username.len() + 1;
+            if start <= userinfo.end {
+                write!(f, "{}***", &s[..start])?;
+                i = userinfo.end;
+            }
+        }
+
+        if let (Some(query), false) = (spans.query, self.params.is_empty()) {
+            f.write_str(&s[i..query.start])?;
+            for (n, param) in s[query.clone()].split('&').enumerate() {
+                if n != 0 {
+                    f.write_str("&")?;
+                }
+                match param.split_once('=') {
+                    Some((name, _)) if self.is_sensitive(name) => write!(f, "{name}=***")?,
+                    _ => f.write_str(param)?,
+                }
+            }
+            i = query.end;
+        }
+
+        f.write_str(&s[i..])
+    }
+}
+
+impl Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Debug::fmt(&self.to_string(), f)
+    }
+}
+
+impl<T: Bos<str>> Debug for HttpUri<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<T: Bos<str>> Display for HttpUri<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<T: Bos<str>> Debug for SpiffeId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<T: Bos<str>> Display for SpiffeId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<O> Debug for IpvFutureRegistry<O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set()
+            .entries(self.entries.iter().map(|(version, _)| version))
+            .finish()
+    }
+}
+
+impl Debug for SchemeRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set()
+            .entries(self.entries.iter().map(|(scheme, _)| scheme))
+            .finish()
+    }
+}
+
 impl Debug for Scheme {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -0,0 +1,465 @@
+//! HTTP-specific utilities.
+
+use crate::{
+    component::{Authority, Host, Scheme},
+    encoding::{
+        encoder::{Path, Query},
+        EStr, Encoder,
+    },
+    error::{
+        EffectiveUriError, EffectiveUriErrorKind, HttpUriError, HttpUriErrorKind, ParseError,
+        ParseErrorKind,
+    },
+    internal::NoInput,
+    parser, Builder, Uri,
+};
+use alloc::{borrow::Cow, string::String};
+use borrow_or_share::Bos;
+use core::hash;
+
+const SCHEME_HTTP: &Scheme = Scheme::new_or_panic("http");
+const SCHEME_HTTPS: &Scheme = Scheme::new_or_panic("https");
+
+/// The [request-target] of an HTTP request.
+///
+/// [request-target]: https://datatracker.ietf.org/doc/html/rfc9112#section-3.2
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{component::Scheme, http::RequestTarget};
+///
+/// const SCHEME_HTTP: &Scheme = Scheme::new_or_panic("http");
+///
+/// let target = RequestTarget::parse("GET", "/where?q=now")?;
+/// assert!(matches!(target, RequestTarget::OriginForm { path, query: Some(query) }
+///     if path == "/where" && query == "q=now"));
+///
+/// let uri = target.effective_uri(SCHEME_HTTP, Some("www.example.org:8080")).unwrap();
+/// assert_eq!(uri.as_str(), "http://www.example.org:8080/where?q=now");
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum RequestTarget<'a> {
+    /// The [origin-form], i.e., an absolute path followed by an optional query.
+    ///
+    /// [origin-form]: https://datatracker.ietf.org/doc/html/rfc9112#section-3.2.1
+    OriginForm {
+        /// The absolute path, which is never empty.
+        path: &'a EStr<Path>,
+        /// The optional query.
+        query: Option<&'a EStr<Query>>,
+    },
+    /// The [absolute-form], i.e., a URI without fragment.
+    ///
+    /// [absolute-form]: https://datatracker.ietf.org/doc/html/rfc9112#section-3.2.2
+    AbsoluteForm(Uri<&'a str>),
+    /// The [authority-form], i.e., a host and a port, used only for `CONNECT` requests.
+    ///
+    /// [authority-form]: https://datatracker.ietf.org/doc/html/rfc9112#section-3.2.3
+    AuthorityForm(Authority<'a>),
+    /// The [asterisk-form], i.e., `"*"`, used only for server-wide `OPTIONS` requests.
+    ///
+    /// [asterisk-form]: https://datatracker.ietf.org/doc/html/rfc9112#section-3.2.4
+    Asterisk,
+}
+
+const fn unexpected_char(index: usize) -> ParseError {
+    ParseError {
+        index,
+        kind: ParseErrorKind::UnexpectedChar,
+        input: NoInput,
+    }
+}
+
+impl<'a> RequestTarget<'a> {
+    /// Parses a request-target from a string slice, given the request method.
+    ///
+    /// The form of the request-target is determined as follows:
+    ///
+    /// - If the method is `CONNECT`, the request-target must be in authority-form.
+    /// - Otherwise, if the request-target is `"*"`, the method must be `OPTIONS`.
+    /// - Otherwise, if the request-target starts with `'/'`, it must be in origin-form.
+    /// - Otherwise, it must be in absolute-form.
+    ///
+    /// Method names are compared case-sensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string does not match the grammar
+    /// of the form of request-target determined above.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::http::RequestTarget;
+    ///
+    /// let target = RequestTarget::parse("CONNECT", "www.example.com:80")?;
+    /// assert!(matches!(target, RequestTarget::AuthorityForm(auth)
+    ///     if auth.host() == "www.example.com" && auth.port_to_u16() == Ok(Some(80))));
+    ///
+    /// let target = RequestTarget::parse("GET", "http://www.example.org/pub/WWW/")?;
+    /// assert!(matches!(target, RequestTarget::AbsoluteForm(uri) if uri.path() == "/pub/WWW/"));
+    ///
+    /// assert!(matches!(RequestTarget::parse("OPTIONS", "*")?, RequestTarget::Asterisk));
+    ///
+    /// assert!(RequestTarget::parse("GET", "*").is_err());
+    /// assert!(RequestTarget::parse("CONNECT", "www.example.com").is_err());
+    /// assert!(RequestTarget::parse("GET", "http://example.com/#frag").is_err());
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub fn parse(method: &str, target: &'a str) -> Result<Self, ParseError> {
+        if method == "CONNECT" {
+            return Self::parse_authority_form(target);
+        }
+        if target == "*" {
+            return if method == "OPTIONS" {
+                Ok(Self::Asterisk)
+            } else {
+                Err(unexpected_char(0))
+            };
+        }
+        if target.starts_with('/') {
+            return Self::parse_origin_form(target);
+        }
+
+        let uri = Uri::parse(target)?;
+        if let Some(fragment) = uri.fragment() {
+            return Err(unexpected_char(target.len() - fragment.as_str().len() - 1));
+        }
+        Ok(Self::AbsoluteForm(uri))
+    }
+
+    fn parse_authority_form(target: &'a str) -> Result<Self, ParseError> {
+        let auth = Authority::parse(target)?;
+        if let Some(userinfo) = auth.userinfo() {
+            return Err(unexpected_char(userinfo.as_str().len()));
+        }
+        if auth.port().is_none_or(|port| port.is_empty()) {
+            return Err(unexpected_char(target.len()));
+        }
+        Ok(Self::AuthorityForm(auth))
+    }
+
+    fn parse_origin_form(target: &'a str) -> Result<Self, ParseError> {
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (target, None),
+        };
+
+        parser::parse_with_table(path.as_bytes(), Path::TABLE)?;
+        if let Some(query) = query {
+            parser::parse_with_table(query.as_bytes(), Query::TABLE).map_err(|mut e| {
+                e.index += path.len() + 1;
+                e
+            })?;
+        }
+
+        Ok(Self::OriginForm {
+            path: EStr::new_validated(path),
+            query: query.map(EStr::new_validated),
+        })
+    }
+
+    /// Reconstructs the [target URI] of the request, given the scheme
+    /// of the connection and the optional value of the `Host` header field.
+    ///
+    /// If the request-target is in absolute-form, the `scheme` and `host`
+    /// arguments are ignored and the request-target is returned as the target URI.
+    /// Otherwise, the target URI has the given scheme, an authority taken from
+    /// the request-target in authority-form or else from the `Host` header field,
+    /// and the path and query from the request-target in origin-form
+    /// or else an empty path.
+    ///
+    /// [target URI]: https://datatracker.ietf.org/doc/html/rfc9112#section-3.3
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if any of the following is true:
+    ///
+    /// - The `Host` header field is needed but missing.
+    /// - The `Host` header field value does not match the `uri-host [ ":" port ]` grammar.
+    /// - The target URI has the `http` or `https` scheme but an empty host.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{component::Scheme, http::RequestTarget};
+    ///
+    /// const SCHEME_HTTPS: &Scheme = Scheme::new_or_panic("https");
+    ///
+    /// let target = RequestTarget::parse("OPTIONS", "*")?;
+    /// let uri = target.effective_uri(SCHEME_HTTPS, Some("example.com")).unwrap();
+    /// assert_eq!(uri.as_str(), "https://example.com");
+    ///
+    /// let target = RequestTarget::parse("GET", "/")?;
+    /// assert!(target.effective_uri(SCHEME_HTTPS, None).is_err());
+    /// assert!(target.effective_uri(SCHEME_HTTPS, Some("")).is_err());
+    /// assert!(target.effective_uri(SCHEME_HTTPS, Some("user@example.com")).is_err());
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub fn effective_uri(
+        &self,
+        scheme: &Scheme,
+        host: Option<&str>,
+    ) -> Result<Uri<String>, EffectiveUriError> {
+        let (auth, path, query) = match *self {
+            Self::AbsoluteForm(uri) => {
+                let is_http = uri.scheme() == SCHEME_HTTP || uri.scheme() == SCHEME_HTTPS;
+                if is_http && uri.authority().is_none_or(|auth| auth.host().is_empty()) {
+                    return Err(EffectiveUriError(EffectiveUriErrorKind::EmptyHost));
+                }
+                return Ok(uri.to_owned());
+            }
+            Self::AuthorityForm(auth) => (auth, EStr::EMPTY, None),
+            Self::OriginForm { path, query } => (parse_host_header(host)?, path, query),
+            Self::Asterisk => (parse_host_header(host)?, EStr::EMPTY, None),
+        };
+
+        if (scheme == SCHEME_HTTP || scheme == SCHEME_HTTPS) && auth.host().is_empty() {
+            return Err(EffectiveUriError(EffectiveUriErrorKind::EmptyHost));
+        }
+
+        Ok(Uri::builder()
+            .scheme(scheme)
+            .authority(auth)
+            .path(path)
+            .optional(Builder::query, query)
+            .build()
+            .unwrap())
+    }
+}
+
+fn parse_host_header(host: Option<&str>) -> Result<Authority<'_>, EffectiveUriError> {
+    let host = host.ok_or(EffectiveUriError(EffectiveUriErrorKind::MissingHostHeader))?;
+    match Authority::parse(host) {
+        Ok(auth) if !auth.has_userinfo() => Ok(auth),
+        _ => Err(EffectiveUriError(EffectiveUriErrorKind::InvalidHostHeader)),
+    }
+}
+
+/// Options for validating an HTTP(S) URI.
+///
+/// This struct is used by [`HttpUri::new_with`].
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct HttpUriOptions {
+    allow_userinfo: bool,
+}
+
+impl HttpUriOptions {
+    /// Creates a new set of options that rejects userinfo.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether userinfo is allowed.
+    ///
+    /// Userinfo is deprecated in `http` and `https` URIs ([RFC 9110, Section 4.2.4]).
+    ///
+    /// [RFC 9110, Section 4.2.4]: https://datatracker.ietf.org/doc/html/rfc9110#section-4.2.4
+    pub fn allow_userinfo(self, allow: bool) -> Self {
+        Self {
+            allow_userinfo: allow,
+        }
+    }
+}
+
+/// A URI with the `http` or `https` scheme.
+///
+/// An `HttpUri` is guaranteed to have a non-empty host, a port that is
+/// either absent, empty, or a number in `0..=65535`, and no userinfo
+/// unless allowed by [`HttpUriOptions`].
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{http::HttpUri, Uri};
+///
+/// let uri = HttpUri::parse("HTTPS://example.com/search?q=uri#top")?;
+/// assert_eq!(uri.host(), "example.com");
+/// assert_eq!(uri.port_u16(), 443);
+/// assert!(uri.is_tls());
+/// assert_eq!(uri.path_and_query(), "/search?q=uri");
+///
+/// assert!(HttpUri::parse("ftp://example.com/").is_err());
+/// assert!(HttpUri::parse("http:///path").is_err());
+/// assert!(HttpUri::parse("http://user@example.com/").is_err());
+/// assert!(HttpUri::parse("http://example.com:65536/").is_err());
+/// # Ok::<_, fluent_uri::error::HttpUriError>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct HttpUri<T> {
+    uri: Uri<T>,
+}
+
+impl<'a> HttpUri<&'a str> {
+    /// Parses an HTTP(S) URI from a string slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string is not a valid URI
+    /// or does not satisfy the properties of an `HttpUri`.
+    pub fn parse(s: &'a str) -> Result<Self, HttpUriError> {
+        let uri = Uri::parse(s).map_err(|_| HttpUriError(HttpUriErrorKind::InvalidUri))?;
+        Self::new(uri)
+    }
+}
+
+impl<T: Bos<str>> HttpUri<T> {
+    /// Validates a URI as an HTTP(S) URI, rejecting userinfo.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the URI does not satisfy the properties of an `HttpUri`.
+    pub fn new(uri: Uri<T>) -> Result<Self, HttpUriError> {
+        Self::new_with(uri, HttpUriOptions::new())
+    }
+
+    /// Validates a URI as an HTTP(S) URI with the given options.
+    ///
+    /// The scheme is compared case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the URI does not satisfy the properties of an `HttpUri`.
+    pub fn new_with(uri: Uri<T>, options: HttpUriOptions) -> Result<Self, HttpUriError> {
+        if uri.scheme() != SCHEME_HTTP && uri.scheme() != SCHEME_HTTPS {
+            return Err(HttpUriError(HttpUriErrorKind::NotHttp));
+        }
+        let auth = match uri.authority() {
+            Some(auth) if !auth.host().is_empty() => auth,
+            _ => return Err(HttpUriError(HttpUriErrorKind::EmptyHost)),
+        };
+        if auth.has_userinfo() && !options.allow_userinfo {
+            return Err(HttpUriError(HttpUriErrorKind::Userinfo));
+        }
+        if auth.port_to_u16().is_err() {
+            return Err(HttpUriError(HttpUriErrorKind::InvalidPort));
+        }
+        Ok(Self { uri })
+    }
+
+    /// Returns the URI as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.uri.as_str()
+    }
+
+    /// Returns a reference to the underlying URI.
+    #[must_use]
+    pub fn as_uri(&self) -> &Uri<T> {
+        &self.uri
+    }
+
+    /// Consumes this `HttpUri` and yields the underlying URI.
+    #[must_use]
+    pub fn into_uri(self) -> Uri<T> {
+        self.uri
+    }
+
+    /// Returns the scheme, which is either `http` or `https` case-insensitively.
+    #[must_use]
+    pub fn scheme(&self) -> &Scheme {
+        self.uri.scheme()
+    }
+
+    /// Checks whether the scheme is `https`.
+    #[must_use]
+    pub fn is_tls(&self) -> bool {
+        self.uri.scheme() == SCHEME_HTTPS
+    }
+
+    /// Returns the authority.
+    #[must_use]
+    pub fn authority(&self) -> Authority<'_> {
+        self.uri.authority().unwrap()
+    }
+
+    /// Returns the host, which is never empty.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        self.authority().host()
+    }
+
+    /// Returns the parsed host.
+    #[must_use]
+    pub fn host_parsed(&self) -> Host<'_> {
+        self.authority().host_parsed()
+    }
+
+    /// Returns the port, or the default port of the scheme
+    /// if the port is absent or empty.
+    #[must_use]
+    pub fn port_u16(&self) -> u16 {
+        match self.authority().port_to_u16() {
+            Ok(Some(port)) => port,
+            _ if self.is_tls() => 443,
+            _ => 80,
+        }
+    }
+
+    /// Returns the path.
+    #[must_use]
+    pub fn path(&self) -> &EStr<Path> {
+        self.uri.path()
+    }
+
+    /// Returns the optional query.
+    #[must_use]
+    pub fn query(&self) -> Option<&EStr<Query>> {
+        self.uri.query()
+    }
+
+    /// Returns the path and the optional query, as in the [origin-form]
+    /// of a request-target.
+    ///
+    /// An empty path is replaced with `"/"`.
+    ///
+    /// [origin-form]: https://datatracker.ietf.org/doc/html/rfc9112#section-3.2.1
+    #[must_use]
+    pub fn path_and_query(&self) -> Cow<'_, str> {
+        let spans = self.uri.spans();
+        let end = spans.query.map_or(spans.path.end, |query| query.end);
+        let s = &self.as_str()[spans.path.start..end];
+        if spans.path.is_empty() {
+            Cow::Owned(alloc::format!("/{s}"))
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+}
+
+impl<T: Bos<str>> TryFrom<Uri<T>> for HttpUri<T> {
+    type Error = HttpUriError;
+
+    fn try_from(uri: Uri<T>) -> Result<Self, Self::Error> {
+        Self::new(uri)
+    }
+}
+
+impl<T: Bos<str>, U: Bos<str>> PartialEq<HttpUri<U>> for HttpUri<T> {
+    fn eq(&self, other: &HttpUri<U>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<T: Bos<str>> PartialEq<str> for HttpUri<T> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<T: Bos<str>> PartialEq<&str> for HttpUri<T> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<T: Bos<str>> Eq for HttpUri<T> {}
+
+impl<T: Bos<str>> hash::Hash for HttpUri<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
@@ -69,6 +69,15 @@ pub struct Meta {
     pub query_end: Option<NonZeroUsize>,
 }
 
+impl Meta {
+    pub const EMPTY: Self = Self {
+        scheme_end: None,
+        auth_meta: None,
+        path_bounds: (0, 0),
+        query_end: None,
+    };
+}
+
 #[derive(Clone, Copy, Default)]
 pub struct AuthMeta {
     pub host_bounds: (usize, usize),
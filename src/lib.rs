@@ -26,6 +26,7 @@
 //! [Reference resolution](UriRef::resolve_against). [Normalization](Uri::normalize).
 //! [Percent-decoding](crate::encoding::EStr#examples).
 //! [Percent-encoding](crate::encoding::EString#examples).
+//! [Compile-time parsing](uri!).
 //!
 //! # Terminology
 //!
@@ -52,34 +53,88 @@
 //! [RFC 8820]: https://datatracker.ietf.org/doc/html/rfc8820
 //! [RFC 9413]: https://datatracker.ietf.org/doc/html/rfc9413
 //!
+//! # Minimum supported Rust version
+//!
+//! The minimum supported Rust version (MSRV) is 1.83, as the const parser
+//! behind [`uri!`] and [`Uri::parse_or_panic`] requires mutable references
+//! in `const fn`.
+//!
 //! # Crate features
 //!
 //! - `net` (default): Enables [`std::net`] support.
-//!   Required for IP address fields in [`Host`] and [`Authority::socket_addrs`].
+//!   Required for IP address fields in [`Host`], [`Authority::socket_addrs`],
+//!   and the [`policy`] and [`proxy`] modules.
 //!   Disabling `net` will not affect parsing behavior.
 //!
-//! - `std` (default): Enables [`std`] support. Required for [`Error`] implementations
-//!   and [`Authority::socket_addrs`]. Disabling `std` while enabling `net`
-//!   requires [`core::net`] and a minimum Rust version of `1.77`.
+//! - `std` (default): Enables [`std`] support. Required for [`Error`] implementations,
+//!   [`Authority::socket_addrs`], and [`Uri::from_file_path`] and [`Uri::to_file_path`].
+//!   Disabling `std` while enabling `net`
+//!   requires [`core::net`].
 //!
 //! - `serde`: Enables [`serde`] support. Required for [`Serialize`] and [`Deserialize`]
 //!   implementations.
 //!
+//! - `idna`: Enables internationalized domain name support with bundled Unicode data.
+//!   Required for [`EStr::to_ascii`], [`EStr::to_unicode`], [`Uri::normalize_idna`],
+//!   and the [`punycode`] module.
+//!
+//! - `security`: Enables homograph and confusable detection for hosts.
+//!   Required for [`Host::security_report`] and the [`security`] module.
+//!   Implies `idna`.
+//!
+//! - `psl`: Embeds a snapshot of the [Public Suffix List]. Required for
+//!   [`Host::public_suffix`], [`Host::registrable_domain`], and the [`psl`] module.
+//!   Implies `std` and `idna`.
+//!
+//! - `zone-id`: Enables parsing of IPv6 zone identifiers in IP literals as per
+//!   [RFC 6874], e.g. `[fe80::1%25eth0]`. The zone identifier is exposed in
+//!   [`Host::Ipv6`] and by [`Authority::zone_id`].
+//!
 //! [`Host`]: component::Host
+//! [`Host::Ipv6`]: component::Host::Ipv6
+//! [`Authority::zone_id`]: component::Authority::zone_id
+//! [RFC 6874]: https://datatracker.ietf.org/doc/html/rfc6874
+//! [`Host::security_report`]: component::Host::security_report
+//! [`Host::public_suffix`]: component::Host::public_suffix
+//! [`Host::registrable_domain`]: component::Host::registrable_domain
+//! [Public Suffix List]: https://publicsuffix.org/
 //! [`Authority::socket_addrs`]: component::Authority::socket_addrs
 //! [`Error`]: std::error::Error
+//! [`EStr::to_ascii`]: encoding::EStr::to_ascii
+//! [`EStr::to_unicode`]: encoding::EStr::to_unicode
 
 mod builder;
 #[macro_use]
 mod common;
 pub mod component;
+pub mod conn;
+pub mod csp;
+pub mod dns;
 pub mod encoding;
 pub mod error;
+mod file;
 mod fmt;
+pub mod http;
 mod internal;
+pub mod map;
 mod normalizer;
+pub mod oauth;
+pub mod origin;
 mod parser;
+#[cfg(feature = "net")]
+pub mod policy;
+#[cfg(feature = "net")]
+pub mod proxy;
+#[cfg(feature = "psl")]
+pub mod psl;
+#[cfg(feature = "idna")]
+pub mod punycode;
+pub mod redact;
 mod resolver;
+#[cfg(feature = "security")]
+pub mod security;
+pub mod spiffe;
+pub mod syntax;
 mod uri;
 
 pub use builder::Builder;
@@ -0,0 +1,276 @@
+//! Offset maps between the input and output of normalization and resolution.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The string from which a byte in the output originates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// The URI (reference) on which the method is called.
+    Input,
+    /// The base URI against which the URI reference is resolved.
+    Base,
+}
+
+/// A map from byte indexes in an output URI (reference) to byte ranges
+/// in the strings from which it is produced.
+///
+/// This struct is created by the `normalize_with_map` and `resolve_against_with_map`
+/// methods on [`Uri`] and [`UriRef`].
+///
+/// The map is stored as a list of runs. Within a run whose output length equals
+/// the length of its source range, every output byte maps to exactly one source byte.
+/// Otherwise, every output byte in the run maps to the whole source range, as is the
+/// case for a decoded percent-encoded octet or a rewritten IPv6 address.
+///
+/// Some output bytes do not originate from any source, such as the `"/."`
+/// prepended to a path that would otherwise start with `"//"`.
+///
+/// [`Uri`]: crate::Uri
+/// [`UriRef`]: crate::UriRef
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{map::Source, UriRef};
+///
+/// let s = "HTTP://a/b/../%63?%7e";
+/// let (normalized, map) = UriRef::parse(s)?.normalize_with_map();
+/// assert_eq!(normalized, "http://a/c?~");
+///
+/// // 'c' is decoded from "%63".
+/// assert_eq!(map.map(9), Some((Source::Input, 14..17)));
+/// assert_eq!(&s[14..17], "%63");
+///
+/// // The path "/c" comes from "/b/../%63".
+/// assert_eq!(map.map_range(8..10), Some((Source::Input, 8..17)));
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OffsetMap {
+    runs: Vec<Run>,
+    len: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Run {
+    out_start: usize,
+    src: Option<(Source, Range<usize>)>,
+}
+
+impl OffsetMap {
+    fn from_entries(entries: &[Option<Entry>]) -> Self {
+        let mut runs: Vec<Run> = Vec::new();
+
+        for (i, entry) in entries.iter().enumerate() {
+            if let Some(run) = runs.last_mut() {
+                let out_len = i - run.out_start;
+                match (&mut run.src, entry) {
+                    (None, None) => continue,
+                    (Some((src, range)), Some(e)) if *src == e.src => {
+                        if out_len == range.len() && range.end == e.start && e.end == e.start + 1 {
+                            range.end = e.end;
+                            continue;
+                        }
+                        if range.start == e.start && range.end == e.end {
+                            continue;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            runs.push(Run {
+                out_start: i,
+                src: entry.map(|e| (e.src, e.start..e.end)),
+            });
+        }
+
+        OffsetMap {
+            runs,
+            len: entries.len(),
+        }
+    }
+
+    /// Returns the length of the output in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks whether the output is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Maps a byte index in the output to the source and the byte range
+    /// within the source from which the byte originates.
+    ///
+    /// Returns `None` if the index is out of bounds or if the byte
+    /// does not originate from any source.
+    #[must_use]
+    pub fn map(&self, i: usize) -> Option<(Source, Range<usize>)> {
+        if i >= self.len {
+            return None;
+        }
+        let run_i = self.runs.partition_point(|run| run.out_start <= i) - 1;
+        let run = &self.runs[run_i];
+        let (src, range) = run.src.clone()?;
+
+        let out_end = self.run_end(run_i);
+        if out_end - run.out_start == range.len() {
+            let start = range.start + (i - run.out_start);
+            Some((src, start..start + 1))
+        } else {
+            Some((src, range))
+        }
+    }
+
+    /// Maps a byte range in the output to the source and the smallest byte range
+    /// within the source that covers every byte from which the output range originates.
+    ///
+    /// Returns `None` if the range is out of bounds, if no byte in the range
+    /// originates from any source, or if the bytes in the range originate
+    /// from more than one source.
+    #[must_use]
+    pub fn map_range(&self, range: Range<usize>) -> Option<(Source, Range<usize>)> {
+        if range.start >= range.end || range.end > self.len {
+            return None;
+        }
+
+        let mut res: Option<(Source, Range<usize>)> = None;
+        let first_run_i = self
+            .runs
+            .partition_point(|run| run.out_start <= range.start)
+            - 1;
+
+        for run_i in first_run_i..self.runs.len() {
+            let run = &self.runs[run_i];
+            if run.out_start >= range.end {
+                break;
+            }
+            let Some((src, src_range)) = &run.src else {
+                continue;
+            };
+
+            let out_end = self.run_end(run_i);
+            let mapped = if out_end - run.out_start == src_range.len() {
+                let start = range.start.max(run.out_start) - run.out_start;
+                let end = range.end.min(out_end) - run.out_start;
+                src_range.start + start..src_range.start + end
+            } else {
+                src_range.clone()
+            };
+
+            match &mut res {
+                None => res = Some((*src, mapped)),
+                Some((res_src, res_range)) => {
+                    if res_src != src {
+                        return None;
+                    }
+                    res_range.start = res_range.start.min(mapped.start);
+                    res_range.end = res_range.end.max(mapped.end);
+                }
+            }
+        }
+        res
+    }
+
+    fn run_end(&self, run_i: usize) -> usize {
+        self.runs
+            .get(run_i + 1)
+            .map_or(self.len, |run| run.out_start)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Entry {
+    src: Source,
+    start: usize,
+    end: usize,
+}
+
+/// Tracks the origin of each byte written to an output buffer.
+///
+/// The unit type tracks nothing and is used when no map is requested.
+pub(crate) trait Track: Default {
+    /// Records `len` bytes copied verbatim from `start` in the source.
+    fn copy(&mut self, src: Source, start: usize, len: usize);
+
+    /// Records `out_len` bytes produced from the whole source range `start..end`.
+    fn squash(&mut self, src: Source, start: usize, end: usize, out_len: usize);
+
+    /// Records `len` bytes not originating from any source.
+    fn insert(&mut self, len: usize);
+
+    /// Shortens the tracked output to `len` bytes.
+    fn truncate(&mut self, len: usize);
+
+    /// Records bytes whose origins are tracked by `other` in the range `start..end`.
+    fn extend_from(&mut self, other: &Self, start: usize, end: usize);
+
+    fn copied(src: Source, start: usize, len: usize) -> Self {
+        let mut t = Self::default();
+        t.copy(src, start, len);
+        t
+    }
+}
+
+impl Track for () {
+    #[inline]
+    fn copy(&mut self, _: Source, _: usize, _: usize) {}
+
+    #[inline]
+    fn squash(&mut self, _: Source, _: usize, _: usize, _: usize) {}
+
+    #[inline]
+    fn insert(&mut self, _: usize) {}
+
+    #[inline]
+    fn truncate(&mut self, _: usize) {}
+
+    #[inline]
+    fn extend_from(&mut self, (): &(), _: usize, _: usize) {}
+}
+
+/// Tracks the origin of each byte individually.
+#[derive(Default)]
+pub(crate) struct Tracker {
+    entries: Vec<Option<Entry>>,
+}
+
+impl Tracker {
+    pub(crate) fn finish(&self) -> OffsetMap {
+        OffsetMap::from_entries(&self.entries)
+    }
+}
+
+impl Track for Tracker {
+    fn copy(&mut self, src: Source, start: usize, len: usize) {
+        self.entries.extend((start..start + len).map(|i| {
+            Some(Entry {
+                src,
+                start: i,
+                end: i + 1,
+            })
+        }));
+    }
+
+    fn squash(&mut self, src: Source, start: usize, end: usize, out_len: usize) {
+        let entry = Entry { src, start, end };
+        self.entries
+            .extend(core::iter::repeat_n(Some(entry), out_len));
+    }
+
+    fn insert(&mut self, len: usize) {
+        self.entries.extend(core::iter::repeat_n(None, len));
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.entries.truncate(len);
+    }
+
+    fn extend_from(&mut self, other: &Self, start: usize, end: usize) {
+        self.entries.extend_from_slice(&other.entries[start..end]);
+    }
+}
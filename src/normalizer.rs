@@ -2,26 +2,70 @@ use crate::{
     common::Ref,
     encoding::{decode_octet, table::UNRESERVED},
     internal::{HostMeta, Meta},
+    map::{Source, Track},
     parser, resolver,
 };
 use alloc::string::String;
 use core::{fmt::Write, num::NonZeroUsize};
 
-pub(crate) fn normalize(r: Ref<'_, '_>) -> (String, Meta) {
+#[cfg(feature = "idna")]
+use crate::{
+    encoding::{encoder::RegName, EStr},
+    error::IdnaError,
+};
+
+#[cfg(feature = "idna")]
+pub(crate) fn normalize_idna(r: Ref<'_, '_>) -> Result<(String, Meta), IdnaError> {
+    let (buf, mut meta) = normalize(r, &mut ());
+
+    let Some(auth_meta) = &mut meta.auth_meta else {
+        return Ok((buf, meta));
+    };
+    let (start, end) = auth_meta.host_bounds;
+    if !matches!(auth_meta.host_meta, HostMeta::RegName) {
+        return Ok((buf, meta));
+    }
+    let host = EStr::<RegName>::new_validated(&buf[start..end]);
+    if host.decode().as_bytes().is_ascii() {
+        return Ok((buf, meta));
+    }
+
+    let ascii = host.to_ascii()?;
+    let mut out = String::with_capacity(buf.len() - host.len() + ascii.len());
+    out.push_str(&buf[..start]);
+    out.push_str(ascii.as_str());
+    let new_end = out.len();
+    out.push_str(&buf[end..]);
+
+    // UTS #46 mapping may turn a registered name into an IPv4 address.
+    auth_meta.host_meta = parser::parse_v4_or_reg_name(ascii.as_str().as_bytes());
+    auth_meta.host_bounds.1 = new_end;
+    let shift = |i: usize| i - end + new_end;
+    meta.path_bounds = (shift(meta.path_bounds.0), shift(meta.path_bounds.1));
+    meta.query_end = meta
+        .query_end
+        .map(|i| NonZeroUsize::new(shift(i.get())).unwrap());
+    Ok((out, meta))
+}
+
+pub(crate) fn normalize<T: Track>(r: Ref<'_, '_>, t: &mut T) -> (String, Meta) {
     // For "a://[::ffff:5:9]/" the capacity is not enough,
     // but it's fine since this rarely happens.
     let mut buf = String::with_capacity(r.as_str().len());
+    let spans = r.spans();
 
     let path = r.path().as_str();
     let mut path_buf = String::with_capacity(path.len());
+    let mut path_t = T::default();
 
     if r.has_scheme() && path.starts_with('/') {
-        normalize_estr(&mut buf, path, false);
-        resolver::remove_dot_segments(&mut path_buf, &buf);
+        let mut buf_t = T::default();
+        normalize_estr(&mut buf, &mut buf_t, path, spans.path.start, false);
+        resolver::remove_dot_segments(&mut path_buf, &mut path_t, &buf, &buf_t);
         buf.clear();
     } else {
         // Don't remove dot segments from relative reference or rootless path.
-        normalize_estr(&mut path_buf, path, false);
+        normalize_estr(&mut path_buf, &mut path_t, path, spans.path.start, false);
     }
 
     let mut meta = Meta::default();
@@ -31,39 +75,53 @@ pub(crate) fn normalize(r: Ref<'_, '_>) -> (String, Meta) {
         buf.make_ascii_lowercase();
         meta.scheme_end = NonZeroUsize::new(buf.len());
         buf.push(':');
+        t.copy(Source::Input, 0, buf.len());
     }
 
     if let Some(auth) = r.authority() {
+        let auth_start = spans.authority.unwrap().start;
         buf.push_str("//");
+        t.copy(Source::Input, auth_start - 2, 2);
 
         if let Some(userinfo) = auth.userinfo() {
-            normalize_estr(&mut buf, userinfo.as_str(), false);
+            normalize_estr(&mut buf, t, userinfo.as_str(), auth_start, false);
             buf.push('@');
+            t.copy(Source::Input, auth_start + userinfo.len(), 1);
         }
 
+        let host_start = spans.host.unwrap().start;
         let mut auth_meta = auth.meta();
         auth_meta.host_bounds.0 = buf.len();
         match auth_meta.host_meta {
             // An IPv4 address is always canonical.
-            HostMeta::Ipv4(..) => buf.push_str(auth.host()),
+            HostMeta::Ipv4(..) => {
+                buf.push_str(auth.host());
+                t.copy(Source::Input, host_start, auth.host().len());
+            }
             #[cfg(feature = "net")]
-            HostMeta::Ipv6(addr) => write!(buf, "[{addr}]").unwrap(),
+            HostMeta::Ipv6(addr) => {
+                let start = buf.len();
+                write!(buf, "[{addr}").unwrap();
+                finish_v6(&mut buf, t, auth.host(), host_start, start);
+            }
             #[cfg(not(feature = "net"))]
             HostMeta::Ipv6() => {
+                let start = buf.len();
                 buf.push('[');
                 write_v6(&mut buf, parser::parse_v6(&auth.host().as_bytes()[1..]));
-                buf.push(']');
+                finish_v6(&mut buf, t, auth.host(), host_start, start);
             }
             HostMeta::IpvFuture => {
                 let start = buf.len();
                 buf.push_str(auth.host());
+                t.copy(Source::Input, host_start, auth.host().len());
 
                 buf[start..].make_ascii_lowercase();
             }
             HostMeta::RegName => {
                 let start = buf.len();
                 let host = auth.host();
-                normalize_estr(&mut buf, host, true);
+                normalize_estr(&mut buf, t, host, host_start, true);
 
                 if buf.len() < start + host.len() {
                     // Only reparse when the length is less than before.
@@ -78,6 +136,7 @@ pub(crate) fn normalize(r: Ref<'_, '_>) -> (String, Meta) {
             if !port.is_empty() {
                 buf.push(':');
                 buf.push_str(port.as_str());
+                t.copy(Source::Input, spans.port.unwrap().start - 1, port.len() + 1);
             }
         }
     }
@@ -86,25 +145,60 @@ pub(crate) fn normalize(r: Ref<'_, '_>) -> (String, Meta) {
     // Make sure that the output is a valid URI reference.
     if r.has_scheme() && !r.has_authority() && path_buf.starts_with("//") {
         buf.push_str("/.");
+        t.insert(2);
     }
     buf.push_str(&path_buf);
+    t.extend_from(&path_t, 0, path_buf.len());
     meta.path_bounds.1 = buf.len();
 
     if let Some(query) = r.query() {
+        let start = spans.query.unwrap().start;
         buf.push('?');
-        normalize_estr(&mut buf, query.as_str(), false);
+        t.copy(Source::Input, start - 1, 1);
+        normalize_estr(&mut buf, t, query.as_str(), start, false);
         meta.query_end = NonZeroUsize::new(buf.len());
     }
 
     if let Some(fragment) = r.fragment() {
+        let start = spans.fragment.unwrap().start;
         buf.push('#');
-        normalize_estr(&mut buf, fragment.as_str(), false);
+        t.copy(Source::Input, start - 1, 1);
+        normalize_estr(&mut buf, t, fragment.as_str(), start, false);
     }
 
     (buf, meta)
 }
 
-fn normalize_estr(buf: &mut String, s: &str, to_lowercase: bool) {
+/// Writes the zone identifier, if any, and the closing bracket
+/// of an IPv6 address whose opening part is written from `start`.
+fn finish_v6<T: Track>(buf: &mut String, t: &mut T, host: &str, host_start: usize, start: usize) {
+    let host_end = host_start + host.len();
+    let Some(addr_end) = host.find('%') else {
+        buf.push(']');
+        t.squash(Source::Input, host_start, host_end, buf.len() - start);
+        return;
+    };
+    let zone_start = host_start + addr_end;
+    t.squash(Source::Input, host_start, zone_start, buf.len() - start);
+
+    // The "%25" delimiter.
+    buf.push_str("%25");
+    t.copy(Source::Input, zone_start, 3);
+    let zone_id = &host[addr_end + 3..host.len() - 1];
+    normalize_estr(buf, t, zone_id, zone_start + 3, false);
+
+    buf.push(']');
+    t.copy(Source::Input, host_end - 1, 1);
+}
+
+// `offset` is the index of `s` within the input, used for tracking.
+fn normalize_estr<T: Track>(
+    buf: &mut String,
+    t: &mut T,
+    s: &str,
+    offset: usize,
+    to_lowercase: bool,
+) {
     let s = s.as_bytes();
     let mut i = 0;
 
@@ -118,10 +212,12 @@ fn normalize_estr(buf: &mut String, s: &str, to_lowercase: bool) {
                     octet = octet.to_ascii_lowercase();
                 }
                 buf.push(octet as char);
+                t.squash(Source::Input, offset + i, offset + i + 3, 1);
             } else {
                 buf.push('%');
                 buf.push(hi.to_ascii_uppercase() as char);
                 buf.push(lo.to_ascii_uppercase() as char);
+                t.copy(Source::Input, offset + i, 3);
             }
             i += 3;
         } else {
@@ -129,6 +225,7 @@ fn normalize_estr(buf: &mut String, s: &str, to_lowercase: bool) {
                 x = x.to_ascii_lowercase();
             }
             buf.push(x as char);
+            t.copy(Source::Input, offset + i, 1);
             i += 1;
         }
     }
@@ -0,0 +1,187 @@
+//! OAuth 2.0 redirect URI matching.
+//!
+//! An authorization server must compare the redirect URI in an authorization
+//! request with the URIs registered for the client using simple string comparison
+//! ([RFC 6749, Section 3.1.2.3]), except that any port is allowed for loopback
+//! IP redirect URIs used by native apps ([RFC 8252, Section 7.3]).
+//!
+//! [RFC 6749, Section 3.1.2.3]: https://datatracker.ietf.org/doc/html/rfc6749#section-3.1.2.3
+//! [RFC 8252, Section 7.3]: https://datatracker.ietf.org/doc/html/rfc8252#section-7.3
+
+use crate::{
+    error::{RedirectUriError, RedirectUriErrorKind},
+    Uri,
+};
+use borrow_or_share::Bos;
+
+/// Checks whether a URI may be used as a redirect URI.
+///
+/// A redirect URI must not contain a fragment ([RFC 6749, Section 3.1.2])
+/// or userinfo, and its host and path must not contain the wildcard
+/// character `'*'`.
+///
+/// Private-use URI schemes for native apps ([RFC 8252, Section 7.1]),
+/// such as `com.example.app:/oauth2redirect`, are allowed.
+///
+/// [RFC 6749, Section 3.1.2]: https://datatracker.ietf.org/doc/html/rfc6749#section-3.1.2
+/// [RFC 8252, Section 7.1]: https://datatracker.ietf.org/doc/html/rfc8252#section-7.1
+///
+/// # Errors
+///
+/// Returns `Err` if the URI is not allowed as a redirect URI.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{oauth::validate_redirect_uri, Uri};
+///
+/// assert!(validate_redirect_uri(&Uri::parse("https://app.example/cb")?).is_ok());
+/// assert!(validate_redirect_uri(&Uri::parse("com.example.app:/cb")?).is_ok());
+///
+/// assert!(validate_redirect_uri(&Uri::parse("https://app.example/cb#x")?).is_err());
+/// assert!(validate_redirect_uri(&Uri::parse("https://u@app.example/cb")?).is_err());
+/// assert!(validate_redirect_uri(&Uri::parse("https://*.app.example/cb")?).is_err());
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+pub fn validate_redirect_uri<T: Bos<str>>(uri: &Uri<T>) -> Result<(), RedirectUriError> {
+    if uri.has_fragment() {
+        return Err(RedirectUriError(RedirectUriErrorKind::Fragment));
+    }
+    if let Some(auth) = uri.authority() {
+        if auth.has_userinfo() {
+            return Err(RedirectUriError(RedirectUriErrorKind::Userinfo));
+        }
+        if auth.host().contains('*') {
+            return Err(RedirectUriError(RedirectUriErrorKind::Wildcard));
+        }
+    }
+    if uri.path().as_str().contains('*') {
+        return Err(RedirectUriError(RedirectUriErrorKind::Wildcard));
+    }
+    Ok(())
+}
+
+/// A comparator for OAuth 2.0 redirect URIs.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{oauth::RedirectUriMatcher, Uri};
+///
+/// let matcher = RedirectUriMatcher::new();
+/// let matches = |registered, requested| {
+///     matcher.matches(&Uri::parse(registered).unwrap(), &Uri::parse(requested).unwrap())
+/// };
+///
+/// assert!(matches("https://app.example/cb", "https://app.example/cb"));
+/// assert!(!matches("https://app.example/cb", "https://app.example/cb/"));
+/// assert!(!matches("https://app.example/cb", "https://APP.example/cb"));
+///
+/// assert!(matches("http://127.0.0.1/cb", "http://127.0.0.1:51004/cb"));
+/// assert!(matches("http://[::1]:8080/cb", "http://[::1]:61023/cb"));
+/// assert!(!matches("http://localhost/cb", "http://localhost:51004/cb"));
+///
+/// assert!(matches("com.example.app:/cb", "com.example.app:/cb"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct RedirectUriMatcher {
+    loopback_any_port: bool,
+}
+
+impl Default for RedirectUriMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedirectUriMatcher {
+    /// Creates a new comparator that allows any port for loopback IP redirect URIs.
+    pub fn new() -> Self {
+        Self {
+            loopback_any_port: true,
+        }
+    }
+
+    /// Sets whether any port is allowed for loopback IP redirect URIs.
+    ///
+    /// A loopback IP redirect URI has the `http` scheme and the host `127.0.0.1`
+    /// or `[::1]`. The name `localhost` is not treated as loopback, as
+    /// recommended by [RFC 8252, Section 8.3].
+    ///
+    /// [RFC 8252, Section 8.3]: https://datatracker.ietf.org/doc/html/rfc8252#section-8.3
+    pub fn loopback_any_port(self, allow: bool) -> Self {
+        Self {
+            loopback_any_port: allow,
+        }
+    }
+
+    /// Compares a requested redirect URI with a registered one.
+    ///
+    /// Both URIs must pass [`validate_redirect_uri`]. They are then compared
+    /// as strings, without any normalization. If any port is allowed for
+    /// loopback IP redirect URIs and both URIs are such URIs, the ports
+    /// are ignored in the comparison.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if either URI is not allowed as a redirect URI
+    /// or the URIs do not match.
+    pub fn check<T: Bos<str>, U: Bos<str>>(
+        &self,
+        registered: &Uri<T>,
+        requested: &Uri<U>,
+    ) -> Result<(), RedirectUriError> {
+        validate_redirect_uri(registered)?;
+        validate_redirect_uri(requested)?;
+
+        if registered.as_str() == requested.as_str()
+            || (self.loopback_any_port
+                && is_loopback(registered)
+                && is_loopback(requested)
+                && without_port(registered) == without_port(requested))
+        {
+            Ok(())
+        } else {
+            Err(RedirectUriError(RedirectUriErrorKind::Mismatch))
+        }
+    }
+
+    /// Checks whether a requested redirect URI matches a registered one.
+    ///
+    /// See [`check`](Self::check) for details.
+    #[must_use]
+    pub fn matches<T: Bos<str>, U: Bos<str>>(
+        &self,
+        registered: &Uri<T>,
+        requested: &Uri<U>,
+    ) -> bool {
+        self.check(registered, requested).is_ok()
+    }
+
+    /// Returns the first registered redirect URI that the requested one matches.
+    #[must_use]
+    pub fn find<'r, T: Bos<str>, U: Bos<str>>(
+        &self,
+        registered: &'r [Uri<T>],
+        requested: &Uri<U>,
+    ) -> Option<&'r Uri<T>> {
+        registered.iter().find(|uri| self.matches(uri, requested))
+    }
+}
+
+/// Checks whether a URI is a loopback IP redirect URI.
+fn is_loopback<T: Bos<str>>(uri: &Uri<T>) -> bool {
+    uri.scheme().as_str() == "http"
+        && uri
+            .authority()
+            .is_some_and(|auth| matches!(auth.host(), "127.0.0.1" | "[::1]"))
+}
+
+/// Splits a URI into the parts before and after its port.
+fn without_port<T: Bos<str>>(uri: &Uri<T>) -> (&str, &str) {
+    let s = uri.as_str();
+    let spans = uri.spans();
+    let host_end = spans.host.map_or(spans.path.start, |host| host.end);
+    (&s[..host_end], &s[spans.path.start..])
+}
@@ -0,0 +1,310 @@
+//! Web origins as defined in [RFC 6454].
+//!
+//! [RFC 6454]: https://datatracker.ietf.org/doc/html/rfc6454
+
+use crate::{
+    component::Scheme,
+    error::{ParseError, ParseErrorKind},
+    internal::NoInput,
+    Uri, UriRef,
+};
+use alloc::string::{String, ToString};
+use borrow_or_share::Bos;
+
+/// Returns the default port of a scheme with a known default port.
+pub(crate) fn default_port(scheme: &Scheme) -> Option<u16> {
+    const SCHEMES: [(&Scheme, u16); 5] = [
+        (Scheme::new_or_panic("http"), 80),
+        (Scheme::new_or_panic("https"), 443),
+        (Scheme::new_or_panic("ws"), 80),
+        (Scheme::new_or_panic("wss"), 443),
+        (Scheme::new_or_panic("ftp"), 21),
+    ];
+    SCHEMES
+        .iter()
+        .find(|(s, _)| *s == scheme)
+        .map(|&(_, port)| port)
+}
+
+/// The [origin] of a URI.
+///
+/// This enum is created by [`Uri::origin`] and [`Origin::parse`].
+///
+/// Two opaque origins are never the same origin, even if they are
+/// created from the same URI. Use [`is_same_origin`] to compare origins.
+///
+/// [origin]: https://datatracker.ietf.org/doc/html/rfc6454#section-4
+/// [`is_same_origin`]: Self::is_same_origin
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::Uri;
+///
+/// let a = Uri::parse("https://example.com/a")?.origin();
+/// let b = Uri::parse("HTTPS://EXAMPLE.COM:443/b")?.origin();
+/// assert!(a.is_same_origin(&b));
+/// assert_eq!(b.serialize(), "https://example.com");
+///
+/// let c = Uri::parse("https://example.com:8443/")?.origin();
+/// assert!(!a.is_same_origin(&c));
+/// assert_eq!(c.serialize(), "https://example.com:8443");
+///
+/// let d = Uri::parse("data:text/plain,hello")?.origin();
+/// assert!(!d.is_same_origin(&d));
+/// assert_eq!(d.serialize(), "null");
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub enum Origin {
+    /// An opaque origin, serialized as `"null"`.
+    Opaque,
+    /// A tuple origin consisting of a scheme, a host, and a port.
+    Tuple(TupleOrigin),
+}
+
+/// A tuple origin consisting of a scheme, a host, and a port.
+///
+/// The scheme and the host are normalized, and the default port
+/// of the scheme is applied if the port is absent.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TupleOrigin {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+}
+
+impl TupleOrigin {
+    /// Returns the scheme in lowercase.
+    #[must_use]
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Returns the normalized host.
+    ///
+    /// A registered name is converted to A-labels if the `idna` feature is enabled.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the port, or `None` if it is absent and
+    /// the scheme has no known default port.
+    #[must_use]
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+}
+
+impl Origin {
+    fn from_uri<T: Bos<str>>(uri: &Uri<T>) -> Option<Self> {
+        #[cfg(feature = "idna")]
+        let uri = uri.normalize_idna().ok()?;
+        #[cfg(not(feature = "idna"))]
+        let uri = uri.normalize();
+
+        let auth = uri.authority()?;
+        if auth.host().is_empty() {
+            return None;
+        }
+        let default = default_port(uri.scheme());
+        let port = match auth.port() {
+            Some(port) if !port.is_empty() => Some(port.as_str().parse().ok()?),
+            _ => default,
+        };
+
+        Some(Origin::Tuple(TupleOrigin {
+            scheme: uri.scheme().as_str().into(),
+            host: auth.host().into(),
+            port,
+        }))
+    }
+
+    /// Parses the value of an [`Origin` header field].
+    ///
+    /// The value must be either `"null"` or a serialized tuple origin,
+    /// i.e., `scheme "://" host [ ":" port ]`.
+    ///
+    /// [`Origin` header field]: https://datatracker.ietf.org/doc/html/rfc6454#section-7
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the value is not `"null"` and is not a URI
+    /// consisting of only a scheme, a host, and an optional port.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{origin::Origin, Uri};
+    ///
+    /// let origin = Origin::parse("https://example.com")?;
+    /// let uri = Uri::parse("https://example.com:443/index.html")?;
+    /// assert!(origin.is_same_origin(&uri.origin()));
+    ///
+    /// assert!(matches!(Origin::parse("null")?, Origin::Opaque));
+    ///
+    /// assert!(Origin::parse("https://example.com/").is_err());
+    /// assert!(Origin::parse("https://user@example.com").is_err());
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        if s == "null" {
+            return Ok(Origin::Opaque);
+        }
+        let uri = Uri::parse(s)?;
+        let spans = uri.spans();
+
+        let index = match (spans.authority, spans.userinfo) {
+            (None, _) => Some(spans.path.start),
+            // Points to the '@' delimiter.
+            (_, Some(userinfo)) => Some(userinfo.end),
+            _ if !spans.path.is_empty() => Some(spans.path.start),
+            _ => spans.query.or(spans.fragment).map(|r| r.start - 1),
+        };
+        if let Some(index) = index {
+            return Err(ParseError {
+                index,
+                kind: ParseErrorKind::UnexpectedChar,
+                input: NoInput,
+            });
+        }
+        Ok(uri.origin())
+    }
+
+    /// Serializes the origin as per [Section 6.2 of RFC 6454].
+    ///
+    /// An opaque origin is serialized as `"null"`. A tuple origin is serialized
+    /// as `scheme "://" host`, followed by `":"` and the port if it is not
+    /// the default port of the scheme.
+    ///
+    /// The output is suitable for the `Origin` and `Access-Control-Allow-Origin`
+    /// header fields.
+    ///
+    /// [Section 6.2 of RFC 6454]: https://datatracker.ietf.org/doc/html/rfc6454#section-6.2
+    #[must_use]
+    pub fn serialize(&self) -> String {
+        self.to_string()
+    }
+
+    /// Checks whether two origins are the [same origin].
+    ///
+    /// Returns `false` if either origin is opaque.
+    ///
+    /// [same origin]: https://datatracker.ietf.org/doc/html/rfc6454#section-5
+    #[must_use]
+    pub fn is_same_origin(&self, other: &Origin) -> bool {
+        match (self, other) {
+            (Origin::Tuple(a), Origin::Tuple(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Bos<str>> Uri<T> {
+    /// Computes the [origin] of the URI.
+    ///
+    /// The origin is a tuple origin if the URI has an authority with a non-empty host
+    /// and a valid port. Otherwise, it is opaque. The URI is normalized beforehand,
+    /// and the default port is applied for the schemes `http`, `https`, `ws`,
+    /// `wss`, and `ftp`.
+    ///
+    /// [origin]: https://datatracker.ietf.org/doc/html/rfc6454#section-4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{origin::Origin, Uri};
+    ///
+    /// let Origin::Tuple(origin) = Uri::parse("HTTP://[0:0::1]/")?.origin() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(origin.scheme(), "http");
+    /// assert_eq!(origin.host(), "[::1]");
+    /// assert_eq!(origin.port(), Some(80));
+    ///
+    /// assert!(matches!(Uri::parse("file:///etc/hosts")?.origin(), Origin::Opaque));
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn origin(&self) -> Origin {
+        Origin::from_uri(self).unwrap_or(Origin::Opaque)
+    }
+}
+
+impl<T: Bos<str>> UriRef<T> {
+    /// Checks whether the URI reference is safe to redirect to from the given URI,
+    /// e.g., as the value of a `?next=` parameter after login.
+    ///
+    /// A redirect is considered safe if the reference, resolved against `current`,
+    /// has the same [origin] as `current`, which must be a tuple origin.
+    /// In addition, the following references are always rejected:
+    ///
+    /// - Network-path references (e.g. `"//evil.com"`), even if they
+    ///   point to the same authority, since they are commonly mishandled
+    ///   by applications that emit them in a `Location` header field.
+    /// - References with userinfo (e.g. `"https://user@example.com/"`),
+    ///   which are commonly used in phishing to disguise the real host.
+    /// - References whose path contains a percent-encoded slash or backslash
+    ///   (`"%2F"` or `"%5C"`), which may be decoded by a downstream component
+    ///   into a network-path reference or a backslash that browsers treat as a slash.
+    ///
+    /// References with a scheme such as `javascript:` or `data:` have an opaque
+    /// origin and are thus rejected. A fragment in `current` is ignored.
+    ///
+    /// [origin]: https://datatracker.ietf.org/doc/html/rfc6454#section-4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{Uri, UriRef};
+    ///
+    /// let current = Uri::parse("https://example.com/login")?;
+    /// let safe = |s| UriRef::parse(s).unwrap().is_safe_redirect(&current);
+    ///
+    /// assert!(safe("/account?tab=profile"));
+    /// assert!(safe("settings"));
+    /// assert!(safe("HTTPS://EXAMPLE.COM:443/home"));
+    ///
+    /// assert!(!safe("//evil.com/"));
+    /// assert!(!safe("https://example.com.evil.com/"));
+    /// assert!(!safe("http://example.com/"));
+    /// assert!(!safe("https://u:p@example.com/"));
+    /// assert!(!safe("javascript:alert(1)"));
+    /// assert!(!safe("/%2F%2Fevil.com"));
+    /// # Ok::<_, fluent_uri::error::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn is_safe_redirect<U: Bos<str>>(&self, current: &Uri<U>) -> bool {
+        if self.has_authority() && !self.has_scheme() {
+            return false;
+        }
+        if self.authority().is_some_and(|auth| auth.has_userinfo()) {
+            return false;
+        }
+
+        let path = self.path().as_str().as_bytes();
+        let has_encoded_slash = path.windows(3).any(|w| {
+            w[0] == b'%'
+                && matches!(
+                    (w[1], w[2].to_ascii_lowercase()),
+                    (b'2', b'f') | (b'5', b'c')
+                )
+        });
+        if has_encoded_slash {
+            return false;
+        }
+
+        let base = match current.spans().fragment {
+            Some(fragment) => Uri::parse(&current.as_str()[..fragment.start - 1]),
+            None => Uri::parse(current.as_str()),
+        };
+        let Ok(base) = base else {
+            return false;
+        };
+        let Ok(target) = self.resolve_against(&base) else {
+            return false;
+        };
+        base.origin().is_same_origin(&target.origin())
+    }
+}
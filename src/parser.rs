@@ -2,11 +2,10 @@ use crate::{
     encoding::{table::*, Table, OCTET_TABLE_LO},
     internal::{AuthMeta, Criteria, HostMeta, Meta, NoInput},
 };
-use core::{
-    num::NonZeroUsize,
-    ops::{Deref, DerefMut},
-    str,
-};
+use core::num::NonZeroUsize;
+
+#[cfg(feature = "net")]
+use crate::net::{Ipv4Addr, Ipv6Addr};
 
 type Result<T> = core::result::Result<T, crate::error::ParseError>;
 
@@ -21,13 +20,33 @@ macro_rules! err {
     };
 }
 
-pub(crate) fn parse(bytes: &[u8], criteria: Criteria) -> Result<Meta> {
+/// Unwraps a `Result` or returns early with the error, like `?` in const context.
+macro_rules! tri {
+    ($e:expr) => {
+        match $e {
+            Ok(x) => x,
+            Err(e) => return Err(e),
+        }
+    };
+}
+
+/// Unwraps an `Option` or returns early with `None`, like `?` in const context.
+macro_rules! tri_opt {
+    ($e:expr) => {
+        match $e {
+            Some(x) => x,
+            None => return None,
+        }
+    };
+}
+
+pub(crate) const fn parse(bytes: &[u8], criteria: Criteria) -> Result<Meta> {
     let mut parser = Parser {
         criteria,
         reader: Reader::new(bytes),
-        out: Meta::default(),
+        out: Meta::EMPTY,
     };
-    parser.parse_from_scheme()?;
+    tri!(parser.parse_from_scheme());
     Ok(parser.out)
 }
 
@@ -58,20 +77,6 @@ struct Reader<'a> {
     pos: usize,
 }
 
-impl<'a> Deref for Parser<'a> {
-    type Target = Reader<'a>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.reader
-    }
-}
-
-impl<'a> DerefMut for Parser<'a> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.reader
-    }
-}
-
 enum PathKind {
     General,
     AbEmpty,
@@ -90,38 +95,42 @@ enum Seg {
 }
 
 impl<'a> Reader<'a> {
-    fn new(bytes: &'a [u8]) -> Self {
+    const fn new(bytes: &'a [u8]) -> Self {
         Reader { bytes, pos: 0 }
     }
 
-    fn len(&self) -> usize {
+    const fn len(&self) -> usize {
         self.bytes.len()
     }
 
-    fn has_remaining(&self) -> bool {
+    const fn has_remaining(&self) -> bool {
         self.pos < self.len()
     }
 
-    fn get(&self, i: usize) -> u8 {
+    const fn get(&self, i: usize) -> u8 {
         self.bytes[i]
     }
 
-    fn peek(&self, i: usize) -> Option<u8> {
-        self.bytes.get(self.pos + i).copied()
+    const fn peek(&self, i: usize) -> Option<u8> {
+        if self.pos + i < self.len() {
+            Some(self.get(self.pos + i))
+        } else {
+            None
+        }
     }
 
     // Any call to this method must keep the invariants.
-    fn skip(&mut self, n: usize) {
+    const fn skip(&mut self, n: usize) {
         // INVARIANT: `pos` is non-decreasing.
         self.pos += n;
         debug_assert!(self.pos <= self.len());
     }
 
     // Returns `true` iff any byte is read.
-    fn read(&mut self, table: &Table) -> Result<bool> {
+    const fn read(&mut self, table: &Table) -> Result<bool> {
         let start = self.pos;
         if table.allows_enc() {
-            self.read_enc(table, |_, _| {})?;
+            tri!(self.read_enc(table));
         } else {
             let mut i = self.pos;
             while i < self.len() {
@@ -137,7 +146,7 @@ impl<'a> Reader<'a> {
         Ok(self.pos > start)
     }
 
-    fn read_enc(&mut self, table: &Table, mut f: impl FnMut(usize, u8)) -> Result<()> {
+    const fn read_enc(&mut self, table: &Table) -> Result<()> {
         let mut i = self.pos;
 
         while i < self.len() {
@@ -159,7 +168,6 @@ impl<'a> Reader<'a> {
                 if !table.allows(x) {
                     break;
                 }
-                f(i, x);
                 // INVARIANT: Since `i < len`, it holds that `i + 1 <= len`.
                 i += 1;
             }
@@ -171,17 +179,25 @@ impl<'a> Reader<'a> {
     }
 
     // The string read must be ASCII.
-    fn read_str(&mut self, s: &str) -> bool {
-        if self.bytes[self.pos..].starts_with(s.as_bytes()) {
-            // INVARIANT: The remaining bytes start with `s` so it's fine to skip `s.len()`.
-            self.skip(s.len());
-            true
-        } else {
-            false
+    const fn read_str(&mut self, s: &str) -> bool {
+        let s = s.as_bytes();
+        if self.len() - self.pos < s.len() {
+            return false;
+        }
+
+        let mut i = 0;
+        while i < s.len() {
+            if self.get(self.pos + i) != s[i] {
+                return false;
+            }
+            i += 1;
         }
+        // INVARIANT: The remaining bytes start with `s` so it's fine to skip `s.len()`.
+        self.skip(s.len());
+        true
     }
 
-    fn read_v6(&mut self) -> Option<[u16; 8]> {
+    const fn read_v6(&mut self) -> Option<[u16; 8]> {
         let mut segs = [0; 8];
         let mut ellipsis_i = 8;
 
@@ -208,7 +224,7 @@ impl<'a> Reader<'a> {
                         // Not enough space, triple colons, or no colon.
                         return None;
                     }
-                    let octets = self.read_v4()?.to_be_bytes();
+                    let octets = tri_opt!(self.read_v4()).to_be_bytes();
                     segs[i] = u16::from_be_bytes([octets[0], octets[1]]);
                     segs[i + 1] = u16::from_be_bytes([octets[2], octets[3]]);
                     i += 2;
@@ -230,7 +246,9 @@ impl<'a> Reader<'a> {
             return None;
         } else {
             // Shift the segments after the ellipsis to the right.
-            for j in (ellipsis_i..i).rev() {
+            let mut j = i;
+            while j > ellipsis_i {
+                j -= 1;
                 segs[8 - (i - j)] = segs[j];
                 segs[j] = 0;
             }
@@ -239,25 +257,25 @@ impl<'a> Reader<'a> {
         Some(segs)
     }
 
-    fn read_v6_segment(&mut self) -> Option<Seg> {
+    const fn read_v6_segment(&mut self) -> Option<Seg> {
         let colon = self.read_str(":");
-        if !self.has_remaining() {
-            return colon.then_some(Seg::SingleColon);
-        }
+        let Some(first) = self.peek(0) else {
+            return if colon { Some(Seg::SingleColon) } else { None };
+        };
 
-        let first = self.peek(0).unwrap();
         let mut x = match OCTET_TABLE_LO[first as usize] {
             v if v < 128 => v as u16,
             _ => {
-                return colon.then(|| {
-                    if first == b':' {
-                        // INVARIANT: Skipping ":" is fine.
-                        self.skip(1);
-                        Seg::Ellipsis
-                    } else {
-                        Seg::SingleColon
-                    }
-                });
+                if !colon {
+                    return None;
+                }
+                return if first == b':' {
+                    // INVARIANT: Skipping ":" is fine.
+                    self.skip(1);
+                    Some(Seg::Ellipsis)
+                } else {
+                    Some(Seg::SingleColon)
+                };
             }
         };
         let mut i = 1;
@@ -283,54 +301,67 @@ impl<'a> Reader<'a> {
         Some(Seg::Normal(x, colon))
     }
 
-    fn read_v4_or_reg_name(&mut self) -> Result<HostMeta> {
-        Ok(match (self.read_v4(), self.read(REG_NAME)?) {
+    const fn read_v4_or_reg_name(&mut self) -> Result<HostMeta> {
+        let v4 = self.read_v4();
+        let reg_name = tri!(self.read(REG_NAME));
+        Ok(match (v4, reg_name) {
             (Some(_addr), false) => HostMeta::Ipv4(
                 #[cfg(feature = "net")]
-                _addr.into(),
+                Ipv4Addr::from_bits(_addr),
             ),
             _ => HostMeta::RegName,
         })
     }
 
-    fn read_v4(&mut self) -> Option<u32> {
-        let mut addr = self.read_v4_octet()? << 24;
-        for i in (0..3).rev() {
+    const fn read_v4(&mut self) -> Option<u32> {
+        let mut addr = tri_opt!(self.read_v4_octet()) << 24;
+        let mut i = 3;
+        while i > 0 {
+            i -= 1;
             if !self.read_str(".") {
                 return None;
             }
-            addr |= self.read_v4_octet()? << (i * 8);
+            addr |= tri_opt!(self.read_v4_octet()) << (i * 8);
         }
         Some(addr)
     }
 
-    fn read_v4_octet(&mut self) -> Option<u32> {
-        let mut res = self.peek_digit(0)?;
+    const fn read_v4_octet(&mut self) -> Option<u32> {
+        let mut res = tri_opt!(self.peek_digit(0));
         if res == 0 {
             // INVARIANT: Skipping "0" is fine.
             self.skip(1);
             return Some(0);
         }
 
-        for i in 1..3 {
+        let mut i = 1;
+        while i < 3 {
             let Some(x) = self.peek_digit(i) else {
                 // INVARIANT: Skipping `i` digits is fine.
                 self.skip(i);
                 return Some(res);
             };
             res = res * 10 + x;
+            i += 1;
         }
         // INVARIANT: Skipping 3 digits is fine.
         self.skip(3);
 
-        u8::try_from(res).is_ok().then_some(res)
+        if res <= u8::MAX as u32 {
+            Some(res)
+        } else {
+            None
+        }
     }
 
-    fn peek_digit(&self, i: usize) -> Option<u32> {
-        self.peek(i).and_then(|x| (x as char).to_digit(10))
+    const fn peek_digit(&self, i: usize) -> Option<u32> {
+        match self.peek(i) {
+            Some(x) => (x as char).to_digit(10),
+            None => None,
+        }
     }
 
-    fn read_port(&mut self) {
+    const fn read_port(&mut self) {
         if self.read_str(":") {
             let mut i = 0;
             while self.peek_digit(i).is_some() {
@@ -341,27 +372,33 @@ impl<'a> Reader<'a> {
         }
     }
 
-    fn read_host(&mut self) -> Result<HostMeta> {
-        match self.read_ip_literal()? {
+    const fn read_host(&mut self) -> Result<HostMeta> {
+        match tri!(self.read_ip_literal()) {
             Some(host) => Ok(host),
             None => self.read_v4_or_reg_name(),
         }
     }
 
-    fn read_ip_literal(&mut self) -> Result<Option<HostMeta>> {
+    const fn read_ip_literal(&mut self) -> Result<Option<HostMeta>> {
         if !self.read_str("[") {
             return Ok(None);
         }
 
         let start = self.pos;
 
-        let meta = if let Some(_addr) = self.read_v6() {
+        let meta = if let Some(_segs) = self.read_v6() {
+            #[cfg(feature = "zone-id")]
+            if self.read_str("%25") && !tri!(self.read(ZONE_ID)) {
+                err!(self.pos, UnexpectedChar);
+            }
             HostMeta::Ipv6(
                 #[cfg(feature = "net")]
-                _addr.into(),
+                Ipv6Addr::new(
+                    _segs[0], _segs[1], _segs[2], _segs[3], _segs[4], _segs[5], _segs[6], _segs[7],
+                ),
             )
         } else if self.pos == start {
-            self.read_ipv_future()?;
+            tri!(self.read_ipv_future());
             HostMeta::IpvFuture
         } else {
             err!(start, InvalidIpv6Addr);
@@ -373,11 +410,11 @@ impl<'a> Reader<'a> {
         Ok(Some(meta))
     }
 
-    fn read_ipv_future(&mut self) -> Result<()> {
+    const fn read_ipv_future(&mut self) -> Result<()> {
         if let Some(b'v' | b'V') = self.peek(0) {
             // INVARIANT: Skipping "v" or "V" is fine.
             self.skip(1);
-            if self.read(HEXDIG)? && self.read_str(".") && self.read(IPV_FUTURE)? {
+            if tri!(self.read(HEXDIG)) && self.read_str(".") && tri!(self.read(IPV_FUTURE)) {
                 return Ok(());
             }
         }
@@ -385,46 +422,73 @@ impl<'a> Reader<'a> {
     }
 }
 
-pub(crate) fn parse_v4_or_reg_name(bytes: &[u8]) -> HostMeta {
+pub(crate) const fn parse_v4_or_reg_name(bytes: &[u8]) -> HostMeta {
     let mut reader = Reader::new(bytes);
     match reader.read_v4() {
         Some(_addr) if !reader.has_remaining() => HostMeta::Ipv4(
             #[cfg(feature = "net")]
-            _addr.into(),
+            Ipv4Addr::from_bits(_addr),
         ),
         _ => HostMeta::RegName,
     }
 }
 
+pub(crate) const fn parse_authority(bytes: &[u8]) -> Result<AuthMeta> {
+    let mut reader = Reader::new(bytes);
+    let meta = tri!(reader.read_authority());
+    if reader.has_remaining() {
+        err!(reader.pos, UnexpectedChar);
+    }
+    Ok(meta)
+}
+
+pub(crate) const fn parse_host(bytes: &[u8]) -> Result<HostMeta> {
+    let mut reader = Reader::new(bytes);
+    let meta = tri!(reader.read_host());
+    if reader.has_remaining() {
+        err!(reader.pos, UnexpectedChar);
+    }
+    Ok(meta)
+}
+
+pub(crate) const fn parse_with_table(bytes: &[u8], table: &Table) -> Result<()> {
+    let mut reader = Reader::new(bytes);
+    tri!(reader.read(table));
+    if reader.has_remaining() {
+        err!(reader.pos, UnexpectedChar);
+    }
+    Ok(())
+}
+
 #[cfg(not(feature = "net"))]
 pub(crate) fn parse_v6(bytes: &[u8]) -> [u16; 8] {
     Reader::new(bytes).read_v6().unwrap()
 }
 
-impl<'a> Parser<'a> {
-    fn parse_from_scheme(&mut self) -> Result<()> {
-        self.read(SCHEME)?;
+impl Parser<'_> {
+    const fn parse_from_scheme(&mut self) -> Result<()> {
+        tri!(self.reader.read(SCHEME));
 
-        if self.peek(0) == Some(b':') {
+        if let Some(b':') = self.reader.peek(0) {
             // Scheme starts with a letter.
-            if self.pos > 0 && self.get(0).is_ascii_alphabetic() {
-                self.out.scheme_end = NonZeroUsize::new(self.pos);
+            if self.reader.pos > 0 && self.reader.get(0).is_ascii_alphabetic() {
+                self.out.scheme_end = NonZeroUsize::new(self.reader.pos);
             } else {
                 err!(0, UnexpectedChar);
             }
 
             // INVARIANT: Skipping ":" is fine.
-            self.skip(1);
-            return if self.read_str("//") {
+            self.reader.skip(1);
+            return if self.reader.read_str("//") {
                 self.parse_from_authority()
             } else {
                 self.parse_from_path(PathKind::General)
             };
         } else if self.criteria.must_have_scheme {
-            err!(self.pos, UnexpectedChar);
-        } else if self.pos == 0 {
+            err!(self.reader.pos, UnexpectedChar);
+        } else if self.reader.pos == 0 {
             // Nothing read.
-            if self.read_str("//") {
+            if self.reader.read_str("//") {
                 return self.parse_from_authority();
             }
         }
@@ -432,35 +496,78 @@ impl<'a> Parser<'a> {
         self.parse_from_path(PathKind::ContinuedNoScheme)
     }
 
-    fn parse_from_authority(&mut self) -> Result<()> {
-        let host;
+    const fn parse_from_authority(&mut self) -> Result<()> {
+        self.out.auth_meta = Some(tri!(self.reader.read_authority()));
+        self.parse_from_path(PathKind::AbEmpty)
+    }
 
-        let mut colon_cnt = 0;
-        let mut colon_i = 0;
+    const fn parse_from_path(&mut self, kind: PathKind) -> Result<()> {
+        let r = &mut self.reader;
+        self.out.path_bounds = match kind {
+            PathKind::General => {
+                let start = r.pos;
+                tri!(r.read(PATH));
+                (start, r.pos)
+            }
+            PathKind::AbEmpty => {
+                let start = r.pos;
+                // Either empty or starting with '/'.
+                if tri!(r.read(PATH)) && r.get(start) != b'/' {
+                    err!(start, UnexpectedChar);
+                }
+                (start, r.pos)
+            }
+            PathKind::ContinuedNoScheme => {
+                tri!(r.read(SEGMENT_NZ_NC));
+
+                if let Some(b':') = r.peek(0) {
+                    // In a relative reference, the first path
+                    // segment cannot contain a colon character.
+                    err!(r.pos, UnexpectedChar);
+                }
+
+                tri!(r.read(PATH));
+                (0, r.pos)
+            }
+        };
 
+        if r.read_str("?") {
+            tri!(r.read(QUERY));
+            self.out.query_end = NonZeroUsize::new(r.pos);
+        }
+
+        if r.read_str("#") {
+            tri!(r.read(FRAGMENT));
+        }
+
+        if r.has_remaining() {
+            err!(r.pos, UnexpectedChar);
+        }
+        Ok(())
+    }
+}
+
+impl Reader<'_> {
+    const fn read_authority(&mut self) -> Result<AuthMeta> {
+        let host;
         let auth_start = self.pos;
 
         // `USERINFO` contains userinfo, reg-name, ':', and port.
-        self.read_enc(USERINFO, |i, x| {
-            if x == b':' {
-                colon_cnt += 1;
-                colon_i = i;
-            }
-        })?;
+        tri!(self.read_enc(USERINFO));
 
-        if self.peek(0) == Some(b'@') {
+        if let Some(b'@') = self.peek(0) {
             // Userinfo present.
             // INVARIANT: Skipping "@" is fine.
             self.skip(1);
 
             let host_start = self.pos;
-            let meta = self.read_host()?;
+            let meta = tri!(self.read_host());
             host = (host_start, self.pos, meta);
 
             self.read_port();
         } else if self.pos == auth_start {
             // Nothing read. We're now at the start of an IP literal or the path.
-            if let Some(meta) = self.read_ip_literal()? {
+            if let Some(meta) = tri!(self.read_ip_literal()) {
                 host = (auth_start, self.pos, meta);
                 self.read_port();
             } else {
@@ -469,15 +576,29 @@ impl<'a> Parser<'a> {
             }
         } else {
             // The whole authority read. Try to parse the host and port.
+            let mut colon_cnt = 0;
+            let mut colon_i = 0;
+
+            let mut i = auth_start;
+            while i < self.pos {
+                if self.get(i) == b':' {
+                    colon_cnt += 1;
+                    colon_i = i;
+                }
+                i += 1;
+            }
+
             let host_end = match colon_cnt {
                 // All host.
                 0 => self.pos,
                 // Host and port.
                 1 => {
-                    for i in colon_i + 1..self.pos {
+                    let mut i = colon_i + 1;
+                    while i < self.pos {
                         if !self.get(i).is_ascii_digit() {
                             err!(i, UnexpectedChar);
                         }
+                        i += 1;
                     }
                     colon_i
                 }
@@ -485,58 +606,15 @@ impl<'a> Parser<'a> {
                 _ => err!(colon_i, UnexpectedChar),
             };
 
-            let meta = parse_v4_or_reg_name(&self.bytes[auth_start..host_end]);
+            let (_, rem) = self.bytes.split_at(auth_start);
+            let (host_bytes, _) = rem.split_at(host_end - auth_start);
+            let meta = parse_v4_or_reg_name(host_bytes);
             host = (auth_start, host_end, meta);
         }
 
-        self.out.auth_meta = Some(AuthMeta {
+        Ok(AuthMeta {
             host_bounds: (host.0, host.1),
             host_meta: host.2,
-        });
-        self.parse_from_path(PathKind::AbEmpty)
-    }
-
-    fn parse_from_path(&mut self, kind: PathKind) -> Result<()> {
-        self.out.path_bounds = match kind {
-            PathKind::General => {
-                let start = self.pos;
-                self.read(PATH)?;
-                (start, self.pos)
-            }
-            PathKind::AbEmpty => {
-                let start = self.pos;
-                // Either empty or starting with '/'.
-                if self.read(PATH)? && self.get(start) != b'/' {
-                    err!(start, UnexpectedChar);
-                }
-                (start, self.pos)
-            }
-            PathKind::ContinuedNoScheme => {
-                self.read(SEGMENT_NZ_NC)?;
-
-                if self.peek(0) == Some(b':') {
-                    // In a relative reference, the first path
-                    // segment cannot contain a colon character.
-                    err!(self.pos, UnexpectedChar);
-                }
-
-                self.read(PATH)?;
-                (0, self.pos)
-            }
-        };
-
-        if self.read_str("?") {
-            self.read(QUERY)?;
-            self.out.query_end = NonZeroUsize::new(self.pos);
-        }
-
-        if self.read_str("#") {
-            self.read(FRAGMENT)?;
-        }
-
-        if self.has_remaining() {
-            err!(self.pos, UnexpectedChar);
-        }
-        Ok(())
+        })
     }
 }
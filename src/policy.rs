@@ -0,0 +1,461 @@
+//! Destination policies for guarding against server-side request forgery.
+//!
+//! [`classify`] and [`classify_ip`] classify hosts and IP addresses by their
+//! special-purpose ranges and names, and [`DestinationPolicy`] decides whether
+//! a URI may be used as the destination of an outgoing request.
+
+use crate::{
+    component::Host,
+    error::{ParseError, PolicyError, PolicyErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    Uri,
+};
+use alloc::{collections::BTreeSet, string::String};
+use borrow_or_share::Bos;
+
+#[cfg(feature = "std")]
+use std::{io, net::SocketAddr, vec::Vec};
+
+/// The class of a host as per the IANA special-purpose address registries
+/// and the special-use domain names registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum HostClass {
+    /// A globally reachable address or a registered name without special use.
+    Public,
+    /// The unspecified address `0.0.0.0/8` or `::`.
+    Unspecified,
+    /// A loopback address `127.0.0.0/8` or `::1`, or the name `localhost`
+    /// and its subdomains.
+    Loopback,
+    /// A private address `10.0.0.0/8`, `172.16.0.0/12`, or `192.168.0.0/16` ([RFC 1918]).
+    ///
+    /// [RFC 1918]: https://datatracker.ietf.org/doc/html/rfc1918
+    Private,
+    /// A link-local address `169.254.0.0/16` or `fe80::/10`.
+    LinkLocal,
+    /// A shared address `100.64.0.0/10` used for carrier-grade NAT ([RFC 6598]).
+    ///
+    /// [RFC 6598]: https://datatracker.ietf.org/doc/html/rfc6598
+    SharedAddress,
+    /// A unique local address `fc00::/7` ([RFC 4193]).
+    ///
+    /// [RFC 4193]: https://datatracker.ietf.org/doc/html/rfc4193
+    UniqueLocal,
+    /// An IPv4-mapped IPv6 address `::ffff:0:0/96`.
+    Ipv4Mapped,
+    /// A multicast address `224.0.0.0/4` or `ff00::/8`.
+    Multicast,
+    /// The limited broadcast address `255.255.255.255`.
+    Broadcast,
+    /// A documentation address `192.0.2.0/24`, `198.51.100.0/24`,
+    /// `203.0.113.0/24`, or `2001:db8::/32`.
+    Documentation,
+    /// Any other reserved address, an IPvFuture address, or a registered name
+    /// ending in a number that is not a valid IPv4 address.
+    Reserved,
+    /// A special-use domain name other than `localhost`, such as `*.internal`,
+    /// `*.local`, `*.home.arpa`, `*.test`, `*.invalid`, or `*.example`.
+    SpecialUseName,
+}
+
+impl HostClass {
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Unspecified => "unspecified",
+            Self::Loopback => "loopback",
+            Self::Private => "private",
+            Self::LinkLocal => "link-local",
+            Self::SharedAddress => "shared address",
+            Self::UniqueLocal => "unique local",
+            Self::Ipv4Mapped => "IPv4-mapped",
+            Self::Multicast => "multicast",
+            Self::Broadcast => "broadcast",
+            Self::Documentation => "documentation",
+            Self::Reserved => "reserved",
+            Self::SpecialUseName => "special-use name",
+        }
+    }
+}
+
+/// Special-use domain names other than `localhost`.
+const SPECIAL_USE_NAMES: [&str; 8] = [
+    "alt",
+    "example",
+    "home.arpa",
+    "internal",
+    "invalid",
+    "local",
+    "onion",
+    "test",
+];
+
+/// Classifies a host.
+///
+/// A registered name is percent-decoded, lowercased, and stripped of
+/// a trailing dot before it is matched against special-use names.
+///
+/// A registered name whose last label is numeric, e.g., `127.1`, `2130706433`,
+/// or `0x7f.0.0.1`, is parsed as an IPv4 address as per the [WHATWG URL Standard]
+/// and classified by that address, since many resolvers accept such forms.
+/// If the parsing fails, it is classified as [`HostClass::Reserved`].
+///
+/// [WHATWG URL Standard]: https://url.spec.whatwg.org/#concept-ipv4-parser
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{component::Host, policy::{classify, HostClass}};
+///
+/// assert_eq!(classify(Host::parse("10.1.2.3")?), HostClass::Private);
+/// assert_eq!(classify(Host::parse("[::ffff:127.0.0.1]")?), HostClass::Ipv4Mapped);
+/// assert_eq!(classify(Host::parse("LOCALHOST.")?), HostClass::Loopback);
+/// assert_eq!(classify(Host::parse("0x7f.1")?), HostClass::Loopback);
+/// assert_eq!(classify(Host::parse("db.internal")?), HostClass::SpecialUseName);
+/// assert_eq!(classify(Host::parse("example.com")?), HostClass::Public);
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[must_use]
+pub fn classify(host: Host<'_>) -> HostClass {
+    match host {
+        Host::Ipv4(addr) => classify_ip(addr.into()),
+        Host::Ipv6(addr, _) => classify_ip(addr.into()),
+        Host::IpvFuture(_) => HostClass::Reserved,
+        Host::RegName(name) => {
+            let mut name = name.decode().into_string_lossy().to_lowercase();
+            if name.ends_with('.') {
+                name.pop();
+            }
+            if ends_in_number(&name) {
+                return parse_ipv4_number(&name).map_or(HostClass::Reserved, classify_v4);
+            }
+            if name == "localhost" || name.ends_with(".localhost") {
+                return HostClass::Loopback;
+            }
+            let special = SPECIAL_USE_NAMES.iter().any(|&suffix| {
+                name.strip_suffix(suffix)
+                    .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+            });
+            if special {
+                HostClass::SpecialUseName
+            } else {
+                HostClass::Public
+            }
+        }
+    }
+}
+
+/// Checks whether the last label of a name is numeric.
+fn ends_in_number(name: &str) -> bool {
+    let last = name.rsplit('.').next().unwrap_or_default();
+    if !last.is_empty() && last.bytes().all(|x| x.is_ascii_digit()) {
+        return true;
+    }
+    last.strip_prefix("0x")
+        .is_some_and(|hex| hex.bytes().all(|x| x.is_ascii_hexdigit()))
+}
+
+/// Parses a name ending in a number as an IPv4 address in the way of `inet_aton`,
+/// with up to four decimal, octal, or hexadecimal parts.
+fn parse_ipv4_number(name: &str) -> Option<Ipv4Addr> {
+    fn parse_part(part: &str) -> Option<u32> {
+        let (digits, radix) = if let Some(hex) = part.strip_prefix("0x") {
+            (hex, 16)
+        } else if part.len() > 1 && part.starts_with('0') {
+            (&part[1..], 8)
+        } else {
+            (part, 10)
+        };
+        if digits.is_empty() {
+            return (radix != 10).then_some(0);
+        }
+        if !digits.bytes().all(|x| x.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(digits, radix).ok()
+    }
+
+    let parts = name
+        .split('.')
+        .map(parse_part)
+        .collect::<Option<alloc::vec::Vec<_>>>()?;
+    let (&last, init) = parts.split_last()?;
+    if init.len() > 3 || init.iter().any(|&part| part > 255) {
+        return None;
+    }
+    // The last part fills the remaining bytes.
+    let bits = 8 * (4 - init.len() as u32);
+    if bits < 32 && last >> bits != 0 {
+        return None;
+    }
+    let init = init
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, &part)| acc | (part << (24 - 8 * i)));
+    Some(Ipv4Addr::from_bits(init | last))
+}
+
+/// Classifies an IP address.
+///
+/// An address in the NAT64 well-known prefix `64:ff9b::/96` or the 6to4 prefix
+/// `2002::/16` is classified by the IPv4 address embedded in it, and a Teredo
+/// address in `2001::/32` by its embedded client IPv4 address.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::policy::{classify_ip, HostClass};
+/// use std::net::IpAddr;
+///
+/// let class = |s: &str| classify_ip(s.parse::<IpAddr>().unwrap());
+/// assert_eq!(class("100.64.0.1"), HostClass::SharedAddress);
+/// assert_eq!(class("fd00::1"), HostClass::UniqueLocal);
+/// assert_eq!(class("64:ff9b::169.254.169.254"), HostClass::LinkLocal);
+/// assert_eq!(class("2002:7f00:1::"), HostClass::Loopback);
+/// assert_eq!(class("2606:4700::1111"), HostClass::Public);
+/// ```
+#[must_use]
+pub fn classify_ip(addr: IpAddr) -> HostClass {
+    match addr {
+        IpAddr::V4(addr) => classify_v4(addr),
+        IpAddr::V6(addr) => classify_v6(addr),
+    }
+}
+
+fn classify_v4(addr: Ipv4Addr) -> HostClass {
+    match addr.octets() {
+        [0, ..] => HostClass::Unspecified,
+        [127, ..] => HostClass::Loopback,
+        [10, ..] | [172, 16..=31, ..] | [192, 168, ..] => HostClass::Private,
+        [100, 64..=127, ..] => HostClass::SharedAddress,
+        [169, 254, ..] => HostClass::LinkLocal,
+        [192, 0, 2, _] | [198, 51, 100, _] | [203, 0, 113, _] => HostClass::Documentation,
+        [224..=239, ..] => HostClass::Multicast,
+        [255, 255, 255, 255] => HostClass::Broadcast,
+        [192, 0, 0, _] | [198, 18..=19, ..] | [240..=255, ..] => HostClass::Reserved,
+        _ => HostClass::Public,
+    }
+}
+
+fn classify_v6(addr: Ipv6Addr) -> HostClass {
+    let segs = addr.segments();
+    match segs {
+        [0, 0, 0, 0, 0, 0, 0, 0] => HostClass::Unspecified,
+        [0, 0, 0, 0, 0, 0, 0, 1] => HostClass::Loopback,
+        [0, 0, 0, 0, 0, 0xffff, ..] => HostClass::Ipv4Mapped,
+        [0x64, 0xff9b, 0, 0, 0, 0, hi, lo] => {
+            classify_v4(Ipv4Addr::from_bits((u32::from(hi) << 16) | u32::from(lo)))
+        }
+        [0x2002, hi, lo, ..] => {
+            classify_v4(Ipv4Addr::from_bits((u32::from(hi) << 16) | u32::from(lo)))
+        }
+        // The client address of Teredo is obfuscated by inverting all bits.
+        [0x2001, 0, .., hi, lo] => classify_v4(Ipv4Addr::from_bits(
+            !((u32::from(hi) << 16) | u32::from(lo)),
+        )),
+        [0x2001, 0xdb8, ..] => HostClass::Documentation,
+        [x, ..] if x & 0xfe00 == 0xfc00 => HostClass::UniqueLocal,
+        [x, ..] if x & 0xffc0 == 0xfe80 => HostClass::LinkLocal,
+        [x, ..] if x & 0xff00 == 0xff00 => HostClass::Multicast,
+        // IPv4-compatible, discard-only, and deprecated site-local addresses.
+        [0, 0, 0, 0, 0, 0, ..] | [0x100, 0, 0, 0, ..] => HostClass::Reserved,
+        [x, ..] if x & 0xffc0 == 0xfec0 => HostClass::Reserved,
+        _ => HostClass::Public,
+    }
+}
+
+/// A policy deciding whether a URI may be used as the destination of
+/// an outgoing request.
+///
+/// A host is checked in the following order:
+///
+/// 1. If the host is on the deny list, it is denied.
+/// 2. If the host is on the allow list, it is allowed.
+/// 3. If the [class] of the host is allowed, it is allowed.
+/// 4. Otherwise, it is denied.
+///
+/// Only [`HostClass::Public`] is allowed by default. Hosts on the lists are
+/// matched exactly after normalization, i.e., registered names are
+/// case-insensitive and IPv6 addresses are compared in canonical form.
+///
+/// [class]: classify
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{policy::{DestinationPolicy, HostClass}, Uri};
+///
+/// let policy = DestinationPolicy::new()
+///     .allow_class(HostClass::Documentation)
+///     .allow_host("metadata.internal")?
+///     .deny_host("evil.example.com")?;
+///
+/// assert!(policy.check(&Uri::parse("https://example.com/hook")?).is_ok());
+/// assert!(policy.check(&Uri::parse("http://192.0.2.1/")?).is_ok());
+/// assert!(policy.check(&Uri::parse("http://METADATA.internal/")?).is_ok());
+///
+/// let e = policy.check(&Uri::parse("http://127.0.0.1:8080/")?).unwrap_err();
+/// assert_eq!(e.host_class(), Some(HostClass::Loopback));
+/// assert!(policy.check(&Uri::parse("http://evil.example.com/")?).is_err());
+/// assert!(policy.check(&Uri::parse("file:///etc/passwd")?).is_err());
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct DestinationPolicy {
+    allowed_classes: BTreeSet<HostClass>,
+    allowed_hosts: BTreeSet<String>,
+    denied_hosts: BTreeSet<String>,
+}
+
+impl Default for DestinationPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DestinationPolicy {
+    /// Creates a new policy that allows only public hosts.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            allowed_classes: BTreeSet::from([HostClass::Public]),
+            allowed_hosts: BTreeSet::new(),
+            denied_hosts: BTreeSet::new(),
+        }
+    }
+
+    /// Allows hosts of the given class.
+    #[must_use]
+    pub fn allow_class(mut self, class: HostClass) -> Self {
+        self.allowed_classes.insert(class);
+        self
+    }
+
+    /// Denies hosts of the given class.
+    #[must_use]
+    pub fn deny_class(mut self, class: HostClass) -> Self {
+        self.allowed_classes.remove(&class);
+        self
+    }
+
+    /// Adds a host to the allow list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string is not a valid host.
+    pub fn allow_host(mut self, host: &str) -> Result<Self, ParseError> {
+        self.allowed_hosts
+            .insert(normalize_host(Host::parse(host)?));
+        Ok(self)
+    }
+
+    /// Adds a host to the deny list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string is not a valid host.
+    pub fn deny_host(mut self, host: &str) -> Result<Self, ParseError> {
+        self.denied_hosts.insert(normalize_host(Host::parse(host)?));
+        Ok(self)
+    }
+
+    /// Checks whether a URI may be used as the destination of a request.
+    ///
+    /// This checks only the host as written in the URI. A registered name
+    /// may still resolve to a disallowed address, which is checked by
+    /// [`check_socket_addrs`].
+    ///
+    /// [`check_socket_addrs`]: Self::check_socket_addrs
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the URI has no authority or an empty host,
+    /// or if the host is denied.
+    pub fn check<T: Bos<str>>(&self, uri: &Uri<T>) -> Result<(), PolicyError> {
+        self.check_host(uri).map(|_| ())
+    }
+
+    /// Returns `Ok(true)` if the host is on the allow list.
+    fn check_host<T: Bos<str>>(&self, uri: &Uri<T>) -> Result<bool, PolicyError> {
+        let auth = uri
+            .authority()
+            .filter(|auth| !auth.host().is_empty())
+            .ok_or(PolicyError(PolicyErrorKind::NoHost))?;
+
+        let host = normalize_host(auth.host_parsed());
+        if self.denied_hosts.contains(&host) {
+            return Err(PolicyError(PolicyErrorKind::DeniedHost));
+        }
+        if self.allowed_hosts.contains(&host) {
+            return Ok(true);
+        }
+        self.check_class(classify(auth.host_parsed()))?;
+        Ok(false)
+    }
+
+    fn check_class(&self, class: HostClass) -> Result<(), PolicyError> {
+        if self.allowed_classes.contains(&class) {
+            Ok(())
+        } else {
+            Err(PolicyError(PolicyErrorKind::DisallowedClass(class)))
+        }
+    }
+
+    /// Checks a URI like [`check`] and then resolves its authority with
+    /// [`Authority::socket_addrs`], checking the class of each resolved address.
+    ///
+    /// Returns the resolved addresses if all of them are allowed. Addresses
+    /// of a host on the allow list are not checked. To prevent DNS rebinding,
+    /// connect to the returned addresses rather than resolving the host again.
+    ///
+    /// [`check`]: Self::check
+    /// [`Authority::socket_addrs`]: crate::component::Authority::socket_addrs
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the check fails, the resolution fails, or any resolved
+    /// address is denied. A policy violation is reported as an [`io::Error`] of
+    /// kind [`PermissionDenied`] wrapping a [`PolicyError`].
+    ///
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    #[cfg(feature = "std")]
+    pub fn check_socket_addrs<T: Bos<str>>(
+        &self,
+        uri: &Uri<T>,
+        default_port: u16,
+    ) -> io::Result<Vec<SocketAddr>> {
+        let denied = |e| io::Error::new(io::ErrorKind::PermissionDenied, e);
+
+        let allowed = self.check_host(uri).map_err(denied)?;
+        let addrs: Vec<_> = uri
+            .authority()
+            .unwrap()
+            .socket_addrs(default_port)?
+            .collect();
+        if !allowed {
+            for addr in &addrs {
+                self.check_class(classify_ip(addr.ip())).map_err(denied)?;
+            }
+        }
+        Ok(addrs)
+    }
+}
+
+/// Lowercases a host and canonicalizes an IPv6 address without its zone identifier.
+fn normalize_host(host: Host<'_>) -> String {
+    match host {
+        Host::Ipv6(addr, _) => alloc::format!("[{addr}]"),
+        Host::RegName(name) => {
+            let mut name = name.decode().into_string_lossy().to_lowercase();
+            if name.ends_with('.') {
+                name.pop();
+            }
+            name
+        }
+        Host::Ipv4(addr) => alloc::format!("{addr}"),
+        Host::IpvFuture(addr) => {
+            alloc::format!("[v{}.{}]", addr.version(), addr.address()).to_ascii_lowercase()
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Public Suffix List support.
+//!
+//! A snapshot of the [Public Suffix List] is embedded in the crate and used by
+//! [`Host::public_suffix`], [`Host::registrable_domain`], and [`is_same_site`].
+//! A newer list may be loaded with [`List::parse`] or [`List::from_file`].
+//!
+//! Both the ICANN and the private sections of the list are used.
+//! The embedded list is licensed under the [Mozilla Public License 2.0].
+//!
+//! [Public Suffix List]: https://publicsuffix.org/
+//! [Mozilla Public License 2.0]: https://mozilla.org/MPL/2.0/
+//! [`Host::public_suffix`]: crate::component::Host::public_suffix
+//! [`Host::registrable_domain`]: crate::component::Host::registrable_domain
+
+use crate::{
+    component::{Authority, Host},
+    encoding::{encoder::RegName, EStr},
+    Uri,
+};
+use alloc::{collections::BTreeSet, string::String, vec, vec::Vec};
+use borrow_or_share::Bos;
+use std::{io, path::Path, sync::OnceLock};
+
+static EMBEDDED: OnceLock<List> = OnceLock::new();
+
+/// A parsed Public Suffix List.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{component::Host, psl::List};
+///
+/// let list = List::parse("com\n*.example\n!www.example\n");
+/// let host = Host::parse("a.b.example")?;
+/// assert_eq!(list.public_suffix(host).unwrap(), "b.example");
+/// assert_eq!(list.registrable_domain(host).unwrap(), "a.b.example");
+///
+/// let host = Host::parse("www.example")?;
+/// assert_eq!(list.public_suffix(host).unwrap(), "example");
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct List {
+    // Rules are stored as labels so that the suffixes of a host can be looked
+    // up as subslices of its labels without joining them.
+    rules: BTreeSet<Vec<String>>,
+    wildcards: BTreeSet<Vec<String>>,
+    exceptions: BTreeSet<Vec<String>>,
+}
+
+impl List {
+    /// Returns the embedded snapshot of the Public Suffix List.
+    ///
+    /// The list is parsed on first use.
+    #[must_use]
+    pub fn embedded() -> &'static List {
+        EMBEDDED.get_or_init(|| List::parse(include_str!("public_suffix_list.dat")))
+    }
+
+    /// Parses a list in the [Public Suffix List format].
+    ///
+    /// Comments, empty lines, and anything after the first whitespace
+    /// on a line are ignored. Rules are matched case-insensitively.
+    ///
+    /// [Public Suffix List format]: https://github.com/publicsuffix/list/wiki/Format
+    #[must_use]
+    pub fn parse(s: &str) -> List {
+        let mut list = List::default();
+        for line in s.lines() {
+            let Some(rule) = line.split_whitespace().next() else {
+                continue;
+            };
+            if rule.starts_with("//") {
+                continue;
+            }
+            let rule = rule.to_lowercase();
+            let labels = |rule: &str| rule.split('.').map(String::from).collect();
+            if let Some(rule) = rule.strip_prefix('!') {
+                list.exceptions.insert(labels(rule));
+            } else if let Some(rule) = rule.strip_prefix("*.") {
+                list.wildcards.insert(labels(rule));
+            } else {
+                list.rules.insert(labels(&rule));
+            }
+        }
+        list
+    }
+
+    /// Reads and parses a list from a local file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file cannot be read or is not valid UTF-8.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<List> {
+        std::fs::read_to_string(path).map(|s| List::parse(&s))
+    }
+
+    /// Returns the public suffix of a host, i.e., its labels matched
+    /// by the prevailing rule of the list.
+    ///
+    /// Returns `None` if the host is not a registered name, or if it has a label
+    /// that is empty, does not decode to valid UTF-8, or is an invalid `xn--` label.
+    /// If no rule matches, the last label is the public suffix. The trailing dot
+    /// of a fully qualified name is ignored and not included in the result.
+    #[must_use]
+    pub fn public_suffix<'a>(&self, host: Host<'a>) -> Option<&'a EStr<RegName>> {
+        let Host::RegName(name) = host else {
+            return None;
+        };
+        let name = strip_trailing_dot(name);
+        let n = self.suffix_len(&normalized_labels(name)?);
+        Some(last_labels(name, n))
+    }
+
+    /// Returns the registrable domain of a host, i.e., its public suffix
+    /// and one more label (eTLD+1).
+    ///
+    /// Returns `None` if the host is not a registered name, has a label that
+    /// is empty, does not decode to valid UTF-8, or is an invalid `xn--` label,
+    /// or is itself a public suffix. The trailing dot of a fully qualified name
+    /// is ignored and not included in the result.
+    #[must_use]
+    pub fn registrable_domain<'a>(&self, host: Host<'a>) -> Option<&'a EStr<RegName>> {
+        let Host::RegName(name) = host else {
+            return None;
+        };
+        let name = strip_trailing_dot(name);
+        let labels = normalized_labels(name)?;
+        let n = self.suffix_len(&labels) + 1;
+        (n <= labels.len()).then(|| last_labels(name, n))
+    }
+
+    /// Checks whether two URIs are [same site] as per the list.
+    ///
+    /// See [`is_same_site`] for details.
+    ///
+    /// [same site]: https://html.spec.whatwg.org/multipage/browsers.html#same-site
+    #[must_use]
+    pub fn is_same_site<T: Bos<str>, U: Bos<str>>(&self, a: &Uri<T>, b: &Uri<U>) -> bool {
+        let (a, b) = (a.normalize(), b.normalize());
+        if a.scheme() != b.scheme() {
+            return false;
+        }
+        let (Some(x), Some(y)) = (a.authority(), b.authority()) else {
+            return false;
+        };
+        match (self.site(x), self.site(y)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+
+    /// Returns the registrable domain, or the host if there is none,
+    /// of a normalized authority.
+    fn site(&self, auth: Authority<'_>) -> Option<Vec<String>> {
+        let Host::RegName(name) = auth.host_parsed() else {
+            return Some(vec![auth.host().into()]);
+        };
+        let labels = normalized_labels(strip_trailing_dot(name))?;
+        let n = self.suffix_len(&labels) + 1;
+        Some(labels[labels.len().saturating_sub(n)..].to_vec())
+    }
+
+    /// Returns the number of labels matched by the prevailing rule.
+    fn suffix_len(&self, labels: &[String]) -> usize {
+        // The default rule is "*".
+        let mut len = 1;
+        for i in 1..=labels.len() {
+            let suffix = &labels[labels.len() - i..];
+            if self.exceptions.contains(suffix) {
+                return (i - 1).max(1);
+            }
+            if self.rules.contains(suffix) || (i > 1 && self.wildcards.contains(&suffix[1..])) {
+                len = i;
+            }
+        }
+        len
+    }
+}
+
+/// Checks whether two URIs are [same site] as per the embedded list.
+///
+/// Two URIs are same site if they have the same scheme and either
+/// the same registrable domain or, if there is none, the same host.
+/// Both URIs are normalized before comparison, and registered names
+/// are compared in their Unicode lowercase form.
+///
+/// Returns `false` if either URI has no authority or a registered name
+/// with a label that is empty, does not decode to valid UTF-8, or is
+/// an invalid `xn--` label.
+///
+/// [same site]: https://html.spec.whatwg.org/multipage/browsers.html#same-site
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{psl::is_same_site, Uri};
+///
+/// let a = Uri::parse("https://a.example.co.uk/")?;
+/// let b = Uri::parse("https://B.Example.CO.UK:8443/path")?;
+/// assert!(is_same_site(&a, &b));
+///
+/// let c = Uri::parse("https://other.co.uk/")?;
+/// assert!(!is_same_site(&a, &c));
+///
+/// let d = Uri::parse("http://a.example.co.uk/")?;
+/// assert!(!is_same_site(&a, &d));
+/// # Ok::<_, fluent_uri::error::ParseError>(())
+/// ```
+#[must_use]
+pub fn is_same_site<T: Bos<str>, U: Bos<str>>(a: &Uri<T>, b: &Uri<U>) -> bool {
+    List::embedded().is_same_site(a, b)
+}
+
+/// Splits a registered name into labels that are percent-decoded,
+/// converted from A-labels to Unicode, and lowercased.
+fn normalized_labels(name: &EStr<RegName>) -> Option<Vec<String>> {
+    name.as_str()
+        .split('.')
+        .map(|label| {
+            let label = EStr::<RegName>::new_validated(label);
+            let decoded = label.decode().into_string().ok()?;
+            if decoded.is_empty() {
+                return None;
+            }
+            match decoded.get(..4) {
+                Some(prefix) if prefix.eq_ignore_ascii_case("xn--") => {
+                    idna::punycode::decode_to_string(&decoded[4..].to_ascii_lowercase())
+                }
+                _ => Some(decoded.to_lowercase()),
+            }
+        })
+        .collect()
+}
+
+/// Strips one trailing dot from a registered name, as of a fully qualified name.
+fn strip_trailing_dot(name: &EStr<RegName>) -> &EStr<RegName> {
+    let s = name.as_str();
+    EStr::new_validated(s.strip_suffix('.').unwrap_or(s))
+}
+
+/// Returns the last `n` labels of a registered name.
+fn last_labels(name: &EStr<RegName>, n: usize) -> &EStr<RegName> {
+    let s = name.as_str();
+    let start = s.rmatch_indices('.').nth(n - 1).map_or(0, |(i, _)| i + 1);
+    EStr::new_validated(&s[start..])
+}
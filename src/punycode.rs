@@ -0,0 +1,45 @@
+//! [Punycode] encoding and decoding of domain name labels.
+//!
+//! These functions operate on a single label without the `xn--` prefix
+//! and perform no mapping or validation. To convert whole domain names,
+//! use [`EStr::to_ascii`] and [`EStr::to_unicode`] instead.
+//!
+//! [Punycode]: https://datatracker.ietf.org/doc/html/rfc3492
+//! [`EStr::to_ascii`]: crate::encoding::EStr::to_ascii
+//! [`EStr::to_unicode`]: crate::encoding::EStr::to_unicode
+
+use alloc::string::String;
+
+/// Encodes a label with Punycode.
+///
+/// Returns `None` if the encoded output would overflow.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::punycode;
+///
+/// assert_eq!(punycode::encode("bücher").unwrap(), "bcher-kva");
+/// assert_eq!(punycode::encode("abc").unwrap(), "abc-");
+/// ```
+#[must_use]
+pub fn encode(label: &str) -> Option<String> {
+    idna::punycode::encode_str(label)
+}
+
+/// Decodes a Punycode-encoded label.
+///
+/// Returns `None` if the input is not valid Punycode.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::punycode;
+///
+/// assert_eq!(punycode::decode("bcher-kva").unwrap(), "bücher");
+/// assert!(punycode::decode("bcher-kva!").is_none());
+/// ```
+#[must_use]
+pub fn decode(label: &str) -> Option<String> {
+    idna::punycode::decode_to_string(label)
+}
@@ -2,13 +2,15 @@ use crate::{
     common::Ref,
     error::{ResolveError, ResolveErrorKind},
     internal::Meta,
+    map::{Source, Track},
 };
 use alloc::string::String;
 use core::num::NonZeroUsize;
 
-pub(crate) fn resolve(
+pub(crate) fn resolve<T: Track>(
     base: Ref<'_, '_>,
     /* reference */ r: Ref<'_, '_>,
+    t: &mut T,
 ) -> Result<(String, Meta), ResolveError> {
     if !base.has_scheme() || base.has_fragment() {
         return Err(ResolveError(ResolveErrorKind::InvalidBase));
@@ -32,40 +34,57 @@ pub(crate) fn resolve(
         r.fragment(),
     );
 
+    // The source and the start index of each target component, used for tracking.
+    let (r_spans, base_spans) = (r.spans(), base.spans());
+    let (t_scheme_src, t_authority_src, t_query_src);
+    let mut path_t = T::default();
+    let r_path_t = || T::copied(Source::Input, r_spans.path.start, r_path.len());
+
     if let Some(r_scheme) = r_scheme {
         t_scheme = r_scheme;
+        t_scheme_src = Source::Input;
         t_authority = r_authority;
+        t_authority_src = (Source::Input, r_spans.authority);
         t_path = if r_path.is_absolute() {
             buf.reserve_exact(r_path.len());
-            remove_dot_segments(&mut buf, r_path.as_str())
+            remove_dot_segments(&mut buf, &mut path_t, r_path.as_str(), &r_path_t())
         } else {
+            path_t = r_path_t();
             r_path.as_str()
         };
         t_query = r_query;
+        t_query_src = (Source::Input, r_spans.query);
     } else {
         if r_authority.is_some() {
             t_authority = r_authority;
+            t_authority_src = (Source::Input, r_spans.authority);
             buf.reserve_exact(r_path.len());
-            t_path = remove_dot_segments(&mut buf, r_path.as_str());
+            t_path = remove_dot_segments(&mut buf, &mut path_t, r_path.as_str(), &r_path_t());
             t_query = r_query;
+            t_query_src = (Source::Input, r_spans.query);
         } else {
             if r_path.is_empty() {
                 t_path = base.path().as_str();
+                path_t = T::copied(Source::Base, base_spans.path.start, t_path.len());
                 if r_query.is_some() {
                     t_query = r_query;
+                    t_query_src = (Source::Input, r_spans.query);
                 } else {
                     t_query = base.query();
+                    t_query_src = (Source::Base, base_spans.query);
                 }
             } else {
                 if r_path.is_absolute() {
                     buf.reserve_exact(r_path.len());
-                    t_path = remove_dot_segments(&mut buf, r_path.as_str());
+                    t_path =
+                        remove_dot_segments(&mut buf, &mut path_t, r_path.as_str(), &r_path_t());
                 } else {
                     // Instead of merging the paths, remove dot segments incrementally.
                     let base_path = base.path().as_str();
                     if base_path.is_empty() {
                         buf.reserve_exact(r_path.len() + 1);
                         buf.push('/');
+                        path_t.insert(1);
                     } else {
                         // Make sure that swapping the order of resolution and normalization
                         // does not change the result.
@@ -77,15 +96,29 @@ pub(crate) fn resolve(
                         };
 
                         buf.reserve_exact(base_path_stripped.len() + r_path.len());
-                        remove_dot_segments(&mut buf, base_path_stripped);
+                        let base_path_t = T::copied(
+                            Source::Base,
+                            base_spans.path.start,
+                            base_path_stripped.len(),
+                        );
+                        remove_dot_segments(
+                            &mut buf,
+                            &mut path_t,
+                            base_path_stripped,
+                            &base_path_t,
+                        );
                     }
-                    t_path = remove_dot_segments(&mut buf, r_path.as_str());
+                    t_path =
+                        remove_dot_segments(&mut buf, &mut path_t, r_path.as_str(), &r_path_t());
                 }
                 t_query = r_query;
+                t_query_src = (Source::Input, r_spans.query);
             }
             t_authority = base.authority();
+            t_authority_src = (Source::Base, base_spans.authority);
         }
         t_scheme = base.scheme();
+        t_scheme_src = Source::Base;
     }
     t_fragment = r_fragment;
 
@@ -111,6 +144,7 @@ pub(crate) fn resolve(
     buf.push_str(t_scheme.as_str());
     meta.scheme_end = NonZeroUsize::new(buf.len());
     buf.push(':');
+    t.copy(t_scheme_src, 0, buf.len());
 
     if let Some(authority) = t_authority {
         let mut auth_meta = authority.meta();
@@ -121,25 +155,38 @@ pub(crate) fn resolve(
 
         buf.push_str(authority.as_str());
         meta.auth_meta = Some(auth_meta);
+
+        let (src, span) = t_authority_src;
+        t.copy(src, span.unwrap().start - 2, authority.as_str().len() + 2);
     }
 
     meta.path_bounds.0 = buf.len();
     // Close the loophole in the original algorithm.
     if t_authority.is_none() && t_path.starts_with("//") {
         buf.push_str("/.");
+        t.insert(2);
     }
     buf.push_str(t_path);
+    t.extend_from(&path_t, 0, t_path.len());
     meta.path_bounds.1 = buf.len();
 
     if let Some(query) = t_query {
         buf.push('?');
         buf.push_str(query.as_str());
         meta.query_end = NonZeroUsize::new(buf.len());
+
+        let (src, span) = t_query_src;
+        t.copy(src, span.unwrap().start - 1, query.len() + 1);
     }
 
     if let Some(fragment) = t_fragment {
         buf.push('#');
         buf.push_str(fragment.as_str());
+        t.copy(
+            Source::Input,
+            r_spans.fragment.unwrap().start - 1,
+            fragment.len() + 1,
+        );
     }
 
     debug_assert_eq!(buf.len(), len);
@@ -147,7 +194,14 @@ pub(crate) fn resolve(
     Ok((buf, meta))
 }
 
-pub(crate) fn remove_dot_segments<'a>(buf: &'a mut String, path: &str) -> &'a str {
+// `path_t` tracks the origin of each byte in `path`.
+pub(crate) fn remove_dot_segments<'a, T: Track>(
+    buf: &'a mut String,
+    t: &mut T,
+    path: &str,
+    path_t: &T,
+) -> &'a str {
+    let mut offset = 0;
     for seg in path.split_inclusive('/') {
         let seg_stripped = seg.strip_suffix('/').unwrap_or(seg);
         match classify_segment(seg_stripped) {
@@ -158,8 +212,13 @@ pub(crate) fn remove_dot_segments<'a>(buf: &'a mut String, path: &str) -> &'a st
                     buf.truncate(buf.rfind('/').unwrap() + 1);
                 }
             }
-            SegKind::Normal => buf.push_str(seg),
+            SegKind::Normal => {
+                buf.push_str(seg);
+                t.extend_from(path_t, offset, offset + seg.len());
+            }
         }
+        t.truncate(buf.len());
+        offset += seg.len();
     }
     buf
 }
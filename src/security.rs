@@ -0,0 +1,210 @@
+//! Homograph and confusable detection for hosts.
+//!
+//! See [`Host::security_report`] for details.
+//!
+//! [`Host::security_report`]: crate::component::Host::security_report
+
+use crate::encoding::{encoder::RegName, EStr};
+use alloc::{string::String, vec::Vec};
+use unicode_security::{skeleton, MixedScript};
+
+/// A potential security issue found in a host.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Issue {
+    /// The registered name does not decode to valid UTF-8
+    /// or fails UTS #46 processing.
+    ///
+    /// Other checks on the Unicode form may be incomplete.
+    Malformed,
+    /// An invisible character, such as a zero-width space or a soft hyphen,
+    /// is found after percent-decoding.
+    InvisibleChar(char),
+    /// A bidirectional control character is found after percent-decoding.
+    BidiControl(char),
+    /// A label contains characters from more than one script
+    /// as per [UTS #39 mixed-script detection].
+    ///
+    /// [UTS #39 mixed-script detection]: https://www.unicode.org/reports/tr39/#Mixed_Script_Detection
+    MixedScript {
+        /// The zero-based index of the label.
+        label: usize,
+    },
+    /// A label is written in a single non-Latin script, but every character
+    /// in it is confusable with a Latin character as per [UTS #39 skeletons].
+    ///
+    /// [UTS #39 skeletons]: https://www.unicode.org/reports/tr39/#Confusable_Detection
+    WholeScriptConfusable {
+        /// The zero-based index of the label.
+        label: usize,
+    },
+    /// A non-ASCII label looks like an ASCII label.
+    AsciiLookalike {
+        /// The zero-based index of the label.
+        label: usize,
+        /// The ASCII label that it looks like.
+        lookalike: String,
+    },
+}
+
+/// A report of potential security issues found in a host.
+///
+/// This struct is created by [`Host::security_report`].
+///
+/// [`Host::security_report`]: crate::component::Host::security_report
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SecurityReport {
+    unicode: Option<String>,
+    issues: Vec<Issue>,
+}
+
+impl SecurityReport {
+    /// Returns the Unicode form of the host on which the checks are performed.
+    ///
+    /// Returns `None` if the host is not a registered name or does not decode
+    /// to valid UTF-8, in which case [`Issue::Malformed`] is reported.
+    #[must_use]
+    pub fn unicode(&self) -> Option<&str> {
+        self.unicode.as_deref()
+    }
+
+    /// Returns the issues found, in order of appearance.
+    #[must_use]
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    /// Checks whether no issue is found.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the ASCII domain name that the host looks like, if any.
+    ///
+    /// This is `Some` if at least one label looks like an ASCII label
+    /// and every other label is ASCII.
+    #[must_use]
+    pub fn ascii_lookalike(&self) -> Option<String> {
+        let unicode = self.unicode.as_deref()?;
+        let mut out = String::with_capacity(unicode.len());
+        let mut found = false;
+
+        for (i, label) in unicode.split('.').enumerate() {
+            if i > 0 {
+                out.push('.');
+            }
+            if label.is_ascii() {
+                out.push_str(label);
+                continue;
+            }
+            let lookalike = self.issues.iter().find_map(|issue| match issue {
+                Issue::AsciiLookalike {
+                    label: j,
+                    lookalike,
+                } if *j == i => Some(lookalike),
+                _ => None,
+            })?;
+            out.push_str(lookalike);
+            found = true;
+        }
+        found.then_some(out)
+    }
+}
+
+pub(crate) fn analyze(name: &EStr<RegName>) -> SecurityReport {
+    let mut report = SecurityReport::default();
+
+    let Ok(decoded) = name.decode().into_string() else {
+        report.issues.push(Issue::Malformed);
+        return report;
+    };
+
+    for c in decoded.chars() {
+        if is_bidi_control(c) {
+            report.issues.push(Issue::BidiControl(c));
+        } else if is_invisible(c) {
+            report.issues.push(Issue::InvisibleChar(c));
+        }
+    }
+
+    let (unicode, res) = idna::domain_to_unicode(&decoded);
+    if res.is_err() {
+        report.issues.push(Issue::Malformed);
+    }
+
+    for (i, label) in unicode.split('.').enumerate() {
+        if label.is_ascii() {
+            continue;
+        }
+        let scripts = label.resolve_script_set();
+        if scripts.is_empty() {
+            report.issues.push(Issue::MixedScript { label: i });
+        }
+
+        let Some(lookalike) = ascii_lookalike(label) else {
+            continue;
+        };
+        let mut latin = scripts;
+        latin.intersect_with("a".resolve_script_set());
+        if !scripts.is_empty() && latin.is_empty() {
+            report
+                .issues
+                .push(Issue::WholeScriptConfusable { label: i });
+        }
+        report.issues.push(Issue::AsciiLookalike {
+            label: i,
+            lookalike,
+        });
+    }
+
+    report.unicode = Some(unicode);
+    report
+}
+
+/// Replaces each non-ASCII character with its skeleton, or returns `None`
+/// if any of the skeletons is not ASCII.
+fn ascii_lookalike(label: &str) -> Option<String> {
+    let mut out = String::with_capacity(label.len());
+    let mut buf = [0; 4];
+    for c in label.chars() {
+        if c.is_ascii() {
+            out.push(c);
+            continue;
+        }
+        for sc in skeleton(c.encode_utf8(&mut buf)) {
+            if !sc.is_ascii() {
+                return None;
+            }
+            out.push(sc.to_ascii_lowercase());
+        }
+    }
+    Some(out)
+}
+
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{115F}'
+            | '\u{1160}'
+            | '\u{17B4}'
+            | '\u{17B5}'
+            | '\u{180B}'..='\u{180F}'
+            | '\u{200B}'..='\u{200D}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{3164}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+            | '\u{E0000}'..='\u{E0FFF}'
+    )
+}
@@ -0,0 +1,301 @@
+//! SPIFFE IDs.
+//!
+//! A [SPIFFE ID] identifies a workload within a trust domain, as in
+//! `spiffe://example.org/ns/prod/sa/web`. It is carried in the URI SAN
+//! of an X.509 SVID and validated on every connection in a service mesh.
+//!
+//! [SPIFFE ID]: https://github.com/spiffe/spiffe/blob/main/standards/SPIFFE-ID.md
+
+use crate::{
+    error::{SpiffeIdError, SpiffeIdErrorKind},
+    Uri,
+};
+use alloc::{string::String, vec::Vec};
+use borrow_or_share::Bos;
+use core::hash;
+
+const MAX_ID_LEN: usize = 2048;
+const MAX_TRUST_DOMAIN_LEN: usize = 255;
+
+/// A SPIFFE ID.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::spiffe::SpiffeId;
+///
+/// let id = SpiffeId::parse("spiffe://example.org/ns/prod/sa/web")?;
+/// assert_eq!(id.trust_domain(), "example.org");
+/// assert_eq!(id.path(), "/ns/prod/sa/web");
+/// assert!(id.path_segments().eq(["ns", "prod", "sa", "web"]));
+///
+/// assert!(SpiffeId::parse("spiffe://Example.org/web").is_err());
+/// assert!(SpiffeId::parse("spiffe://example.org:443/web").is_err());
+/// assert!(SpiffeId::parse("spiffe://example.org/a/../web").is_err());
+/// assert!(SpiffeId::parse("spiffe://example.org/web/").is_err());
+/// # Ok::<_, fluent_uri::error::SpiffeIdError>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct SpiffeId<T> {
+    uri: Uri<T>,
+}
+
+impl<'a> SpiffeId<&'a str> {
+    /// Parses a SPIFFE ID from a string slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string is not a valid URI or not a valid SPIFFE ID.
+    pub fn parse(s: &'a str) -> Result<Self, SpiffeIdError> {
+        let uri = Uri::parse(s).map_err(|_| SpiffeIdError(SpiffeIdErrorKind::InvalidUri))?;
+        Self::new(uri)
+    }
+}
+
+impl SpiffeId<String> {
+    /// Creates a new builder for a SPIFFE ID in the given trust domain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::spiffe::SpiffeId;
+    ///
+    /// let id = SpiffeId::builder("example.org")
+    ///     .segment("ns")
+    ///     .segment("prod")
+    ///     .build()?;
+    /// assert_eq!(id.as_str(), "spiffe://example.org/ns/prod");
+    ///
+    /// assert!(SpiffeId::builder("example.org").segment("a/b").build().is_err());
+    /// # Ok::<_, fluent_uri::error::SpiffeIdError>(())
+    /// ```
+    pub fn builder(trust_domain: &str) -> SpiffeIdBuilder {
+        SpiffeIdBuilder {
+            trust_domain: trust_domain.into(),
+            segments: Vec::new(),
+        }
+    }
+}
+
+impl<T: Bos<str>> SpiffeId<T> {
+    /// Validates a URI as a SPIFFE ID.
+    ///
+    /// The URI must satisfy the following rules:
+    ///
+    /// - It is at most 2048 octets long.
+    /// - The scheme is `spiffe` in lowercase.
+    /// - The authority consists of a trust domain only, without userinfo or port.
+    /// - The trust domain is between 1 and 255 octets long, and consists of
+    ///   lowercase ASCII letters, digits, `'.'`, `'-'` and `'_'`.
+    /// - The path is either empty or a sequence of `'/'`-prefixed segments.
+    /// - Each segment is non-empty, is neither `.` nor `..`, and consists of
+    ///   ASCII letters, digits, `'.'`, `'-'` and `'_'`.
+    /// - There is no query or fragment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the URI is not a valid SPIFFE ID.
+    pub fn new(uri: Uri<T>) -> Result<Self, SpiffeIdError> {
+        validate(&uri).map(|()| Self { uri })
+    }
+
+    /// Returns the SPIFFE ID as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.uri.as_str()
+    }
+
+    /// Returns a reference to the underlying URI.
+    #[must_use]
+    pub fn as_uri(&self) -> &Uri<T> {
+        &self.uri
+    }
+
+    /// Consumes this `SpiffeId` and yields the underlying URI.
+    #[must_use]
+    pub fn into_uri(self) -> Uri<T> {
+        self.uri
+    }
+
+    /// Returns the trust domain.
+    #[must_use]
+    pub fn trust_domain(&self) -> &str {
+        self.uri.authority().unwrap().host()
+    }
+
+    /// Returns the path, which is either empty or starts with `'/'`.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        self.uri.path().as_str()
+    }
+
+    /// Returns an iterator over the path segments.
+    ///
+    /// The iterator yields nothing if the path is empty.
+    pub fn path_segments(&self) -> PathSegments<'_> {
+        PathSegments {
+            inner: self.path().strip_prefix('/').map(|path| path.split('/')),
+        }
+    }
+}
+
+impl<T: Bos<str>> TryFrom<Uri<T>> for SpiffeId<T> {
+    type Error = SpiffeIdError;
+
+    fn try_from(uri: Uri<T>) -> Result<Self, Self::Error> {
+        Self::new(uri)
+    }
+}
+
+impl<T: Bos<str>, U: Bos<str>> PartialEq<SpiffeId<U>> for SpiffeId<T> {
+    fn eq(&self, other: &SpiffeId<U>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<T: Bos<str>> PartialEq<str> for SpiffeId<T> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<T: Bos<str>> PartialEq<&str> for SpiffeId<T> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<T: Bos<str>> Eq for SpiffeId<T> {}
+
+impl<T: Bos<str>> hash::Hash for SpiffeId<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+/// An iterator over the path segments of a SPIFFE ID.
+///
+/// This struct is created by [`SpiffeId::path_segments`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PathSegments<'a> {
+    inner: Option<core::str::Split<'a, char>>,
+}
+
+impl<'a> Iterator for PathSegments<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.as_mut()?.next()
+    }
+}
+
+impl DoubleEndedIterator for PathSegments<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.as_mut()?.next_back()
+    }
+}
+
+/// A builder for SPIFFE IDs.
+///
+/// This struct is created by [`SpiffeId::builder`].
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct SpiffeIdBuilder {
+    trust_domain: String,
+    segments: Vec<String>,
+}
+
+impl SpiffeIdBuilder {
+    /// Appends a path segment.
+    ///
+    /// The segment is validated when the SPIFFE ID is built.
+    pub fn segment(mut self, segment: &str) -> Self {
+        self.segments.push(segment.into());
+        self
+    }
+
+    /// Builds the SPIFFE ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the trust domain or any path segment is invalid,
+    /// or if the SPIFFE ID is too long.
+    pub fn build(self) -> Result<SpiffeId<String>, SpiffeIdError> {
+        validate_trust_domain(&self.trust_domain)?;
+
+        let mut s = String::from("spiffe://");
+        s.push_str(&self.trust_domain);
+        for segment in &self.segments {
+            validate_segment(segment)?;
+            s.push('/');
+            s.push_str(segment);
+        }
+
+        let uri = Uri::parse(s).map_err(|_| SpiffeIdError(SpiffeIdErrorKind::InvalidUri))?;
+        SpiffeId::new(uri)
+    }
+}
+
+fn validate<T: Bos<str>>(uri: &Uri<T>) -> Result<(), SpiffeIdError> {
+    if uri.as_str().len() > MAX_ID_LEN {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::TooLong));
+    }
+    if uri.scheme().as_str() != "spiffe" {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::InvalidScheme));
+    }
+
+    let Some(auth) = uri.authority() else {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::EmptyTrustDomain));
+    };
+    if auth.has_userinfo() {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::Userinfo));
+    }
+    if auth.has_port() {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::Port));
+    }
+    validate_trust_domain(auth.host())?;
+
+    if uri.has_query() {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::Query));
+    }
+    if uri.has_fragment() {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::Fragment));
+    }
+
+    // The path is either empty or starts with '/' when authority is present.
+    match uri.path().as_str().strip_prefix('/') {
+        Some(path) => path.split('/').try_for_each(validate_segment),
+        None => Ok(()),
+    }
+}
+
+fn validate_trust_domain(s: &str) -> Result<(), SpiffeIdError> {
+    if s.is_empty() {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::EmptyTrustDomain));
+    }
+    let valid = s.len() <= MAX_TRUST_DOMAIN_LEN
+        && s.bytes().all(|x| {
+            x.is_ascii_lowercase() || x.is_ascii_digit() || matches!(x, b'.' | b'-' | b'_')
+        });
+    if !valid {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::InvalidTrustDomain));
+    }
+    Ok(())
+}
+
+fn validate_segment(s: &str) -> Result<(), SpiffeIdError> {
+    if s.is_empty() {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::EmptySegment));
+    }
+    if s == "." || s == ".." {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::DotSegment));
+    }
+    if !s
+        .bytes()
+        .all(|x| x.is_ascii_alphanumeric() || matches!(x, b'.' | b'-' | b'_'))
+    {
+        return Err(SpiffeIdError(SpiffeIdErrorKind::InvalidPathChar));
+    }
+    Ok(())
+}
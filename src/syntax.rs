@@ -0,0 +1,273 @@
+//! Scheme-specific syntax validation.
+//!
+//! The generic URI syntax of RFC 3986 is refined by each URI scheme.
+//! A [`SchemeSyntax`] validates the scheme-specific rules of a parsed URI
+//! and converts it into a typed value, and a [`SchemeRegistry`] dispatches
+//! validation on the scheme of a URI. Implementations are provided for
+//! [`Http`] and [`File`], and third parties can implement their own.
+
+use crate::{
+    builder::state::{End, To},
+    component::Scheme,
+    error::{SchemeSyntaxError, SchemeSyntaxErrorKind},
+    http::HttpUri,
+    Builder, Uri,
+};
+use alloc::{string::String, vec::Vec};
+use borrow_or_share::Bos;
+
+/// A scheme-specific syntax.
+///
+/// # Examples
+///
+/// Implement a syntax for `urn:uuid` URIs:
+///
+/// ```
+/// use borrow_or_share::Bos;
+/// use fluent_uri::{component::Scheme, error::SchemeSyntaxError, syntax::SchemeSyntax, Uri};
+///
+/// struct UrnUuid;
+///
+/// impl SchemeSyntax for UrnUuid {
+///     const SCHEMES: &'static [&'static Scheme] = &[Scheme::new_or_panic("urn")];
+///     type Output<T: Bos<str>> = Uri<T>;
+///
+///     fn validate<T: Bos<str>>(uri: Uri<T>) -> Result<Uri<T>, SchemeSyntaxError> {
+///         let uuid = uri.path().as_str().strip_prefix("uuid:");
+///         if uuid.is_none_or(|uuid| uuid.len() != 36) {
+///             return Err(SchemeSyntaxError::invalid());
+///         }
+///         Ok(uri)
+///     }
+/// }
+///
+/// assert!(Uri::parse_with::<UrnUuid>("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6").is_ok());
+/// assert!(Uri::parse_with::<UrnUuid>("urn:isbn:0451450523").is_err());
+/// assert!(Uri::parse_with::<UrnUuid>("http://example.com/").is_err());
+/// ```
+pub trait SchemeSyntax {
+    /// The schemes to which the syntax applies, compared case-insensitively.
+    const SCHEMES: &'static [&'static Scheme];
+
+    /// The typed value into which a valid URI is converted.
+    type Output<T: Bos<str>>;
+
+    /// Validates the scheme-specific rules of a URI and converts it
+    /// into a typed value.
+    ///
+    /// The scheme of the URI is guaranteed to be one of [`SCHEMES`]
+    /// when called by [`Uri::validate_with`].
+    ///
+    /// [`SCHEMES`]: Self::SCHEMES
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the URI violates any scheme-specific rule.
+    fn validate<T: Bos<str>>(uri: Uri<T>) -> Result<Self::Output<T>, SchemeSyntaxError>;
+}
+
+/// The syntax of `http` and `https` URIs, validated as [`HttpUri`]s.
+#[derive(Clone, Copy, Debug)]
+pub struct Http;
+
+impl SchemeSyntax for Http {
+    const SCHEMES: &'static [&'static Scheme] =
+        &[Scheme::new_or_panic("http"), Scheme::new_or_panic("https")];
+    type Output<T: Bos<str>> = HttpUri<T>;
+
+    fn validate<T: Bos<str>>(uri: Uri<T>) -> Result<HttpUri<T>, SchemeSyntaxError> {
+        HttpUri::new(uri).map_err(SchemeSyntaxError::from)
+    }
+}
+
+/// The syntax of `file` URIs as per [RFC 8089].
+///
+/// A `file` URI must have no userinfo, port, or query, and its path
+/// must start with `'/'`.
+///
+/// [RFC 8089]: https://datatracker.ietf.org/doc/html/rfc8089#section-2
+#[derive(Clone, Copy, Debug)]
+pub struct File;
+
+impl SchemeSyntax for File {
+    const SCHEMES: &'static [&'static Scheme] = &[Scheme::new_or_panic("file")];
+    type Output<T: Bos<str>> = Uri<T>;
+
+    fn validate<T: Bos<str>>(uri: Uri<T>) -> Result<Uri<T>, SchemeSyntaxError> {
+        if let Some(auth) = uri.authority() {
+            if auth.has_userinfo() {
+                return Err(SchemeSyntaxError(SchemeSyntaxErrorKind::FileUserinfo));
+            }
+            if auth.has_port() {
+                return Err(SchemeSyntaxError(SchemeSyntaxErrorKind::FilePort));
+            }
+        }
+        if !uri.path().is_absolute() {
+            return Err(SchemeSyntaxError(SchemeSyntaxErrorKind::FileNotAbsolute));
+        }
+        if uri.has_query() {
+            return Err(SchemeSyntaxError(SchemeSyntaxErrorKind::FileQuery));
+        }
+        Ok(uri)
+    }
+}
+
+impl<'a> Uri<&'a str> {
+    /// Parses a URI from a string slice and validates it
+    /// with the given scheme-specific syntax.
+    ///
+    /// See [`validate_with`](Self::validate_with) for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the string is not a valid URI or the URI
+    /// does not satisfy the syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{syntax::Http, Uri};
+    ///
+    /// let uri = Uri::parse_with::<Http>("https://example.com/")?;
+    /// assert_eq!(uri.port_u16(), 443);
+    ///
+    /// assert!(Uri::parse_with::<Http>("https:///").is_err());
+    /// assert!(Uri::parse_with::<Http>("ftp://example.com/").is_err());
+    /// # Ok::<_, fluent_uri::error::SchemeSyntaxError>(())
+    /// ```
+    pub fn parse_with<S: SchemeSyntax>(
+        s: &'a str,
+    ) -> Result<S::Output<&'a str>, SchemeSyntaxError> {
+        Uri::parse(s)?.validate_with::<S>()
+    }
+}
+
+impl<T: Bos<str>> Uri<T> {
+    /// Validates the URI with the given scheme-specific syntax
+    /// and converts it into a typed value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the scheme is not one of [`S::SCHEMES`] or
+    /// [`S::validate`] fails.
+    ///
+    /// [`S::SCHEMES`]: SchemeSyntax::SCHEMES
+    /// [`S::validate`]: SchemeSyntax::validate
+    pub fn validate_with<S: SchemeSyntax>(self) -> Result<S::Output<T>, SchemeSyntaxError> {
+        if !S::SCHEMES.iter().any(|&scheme| scheme == self.scheme()) {
+            return Err(SchemeSyntaxError(SchemeSyntaxErrorKind::UnexpectedScheme));
+        }
+        S::validate(self)
+    }
+}
+
+type Validator = fn(Uri<&str>) -> Result<(), SchemeSyntaxError>;
+
+/// A registry of scheme-specific syntaxes.
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{
+///     syntax::{File, Http, SchemeRegistry},
+///     Uri,
+/// };
+///
+/// let registry = SchemeRegistry::new().register::<Http>().register::<File>();
+/// let validate = |s| registry.validate(&Uri::parse(s).unwrap());
+///
+/// assert!(validate("https://example.com/").is_ok());
+/// assert!(validate("file:///etc/hosts").is_ok());
+/// assert!(validate("mailto:user@example.com").is_ok());
+///
+/// assert!(validate("http://user@example.com/").is_err());
+/// assert!(validate("file://host:80/etc/hosts").is_err());
+/// ```
+#[derive(Clone, Default)]
+#[must_use]
+pub struct SchemeRegistry {
+    pub(crate) entries: Vec<(&'static Scheme, Validator)>,
+}
+
+impl SchemeRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with the built-in [`Http`] and [`File`] syntaxes.
+    pub fn builtin() -> Self {
+        Self::new().register::<Http>().register::<File>()
+    }
+
+    /// Registers a syntax for each of its schemes.
+    ///
+    /// A syntax registered later takes precedence over earlier
+    /// ones for the same scheme.
+    pub fn register<S: SchemeSyntax>(mut self) -> Self {
+        for &scheme in S::SCHEMES {
+            self.entries
+                .push((scheme, |uri| S::validate(uri).map(|_| ())));
+        }
+        self
+    }
+
+    /// Checks whether a syntax is registered for the scheme.
+    #[must_use]
+    pub fn contains(&self, scheme: &Scheme) -> bool {
+        self.entries.iter().any(|&(s, _)| s == scheme)
+    }
+
+    /// Validates the URI with the syntax registered for its scheme.
+    ///
+    /// A URI whose scheme has no registered syntax is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the URI does not satisfy the syntax registered
+    /// for its scheme.
+    pub fn validate<T: Bos<str>>(&self, uri: &Uri<T>) -> Result<(), SchemeSyntaxError> {
+        let Some(&(_, validate)) = self.entries.iter().rev().find(|&&(s, _)| s == uri.scheme())
+        else {
+            return Ok(());
+        };
+        validate(uri.as_uri_ref().as_uri().unwrap())
+    }
+}
+
+impl<S: To<End>> Builder<Uri<String>, S> {
+    /// Builds the URI and validates it with the given scheme-specific syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if [`build`](Self::build) or [`Uri::validate_with`] fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_uri::{component::Scheme, encoding::EStr, syntax::File, Uri};
+    ///
+    /// let uri = Uri::builder()
+    ///     .scheme(Scheme::new_or_panic("file"))
+    ///     .path(EStr::new_or_panic("etc/hosts"))
+    ///     .build_with::<File>();
+    /// assert!(uri.is_err());
+    /// ```
+    pub fn build_with<Sx: SchemeSyntax>(self) -> Result<Sx::Output<String>, SchemeSyntaxError> {
+        self.build()?.validate_with::<Sx>()
+    }
+
+    /// Builds the URI and validates it with the syntax registered for its scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if [`build`](Self::build) or [`SchemeRegistry::validate`] fails.
+    pub fn build_with_registry(
+        self,
+        registry: &SchemeRegistry,
+    ) -> Result<Uri<String>, SchemeSyntaxError> {
+        let uri = self.build()?;
+        registry.validate(&uri)?;
+        Ok(uri)
+    }
+}
@@ -36,3 +36,65 @@ ri_maybe_ref! {
     as_method = as_uri_ref,
     into_method = into_uri_ref,
 }
+
+/// Parses a URI at compile time into a `Uri<&'static str>`.
+///
+/// The argument must be a constant expression of type `&'static str`,
+/// usually a string literal. Compilation fails if it is not a valid URI.
+///
+/// This macro is a shorthand for evaluating [`Uri::parse_or_panic`] in a const item.
+///
+/// [`Uri::parse_or_panic`]: crate::Uri::parse_or_panic
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{uri, Uri};
+///
+/// let uri: Uri<&'static str> = uri!("https://example.com/a");
+/// assert_eq!(uri.authority().unwrap().host(), "example.com");
+/// ```
+///
+/// An invalid URI is rejected at compile time:
+///
+/// ```compile_fail
+/// let uri = fluent_uri::uri!("https://exa mple.com/");
+/// ```
+#[macro_export]
+macro_rules! uri {
+    ($s:expr) => {{
+        const URI: $crate::Uri<&'static str> = $crate::Uri::parse_or_panic($s);
+        URI
+    }};
+}
+
+/// Parses a URI reference at compile time into a `UriRef<&'static str>`.
+///
+/// The argument must be a constant expression of type `&'static str`,
+/// usually a string literal. Compilation fails if it is not a valid URI reference.
+///
+/// This macro is a shorthand for evaluating [`UriRef::parse_or_panic`] in a const item.
+///
+/// [`UriRef::parse_or_panic`]: crate::UriRef::parse_or_panic
+///
+/// # Examples
+///
+/// ```
+/// use fluent_uri::{uri_ref, UriRef};
+///
+/// let uri_ref: UriRef<&'static str> = uri_ref!("/path?query");
+/// assert_eq!(uri_ref.path(), "/path");
+/// ```
+///
+/// An invalid URI reference is rejected at compile time:
+///
+/// ```compile_fail
+/// let uri_ref = fluent_uri::uri_ref!("%zz");
+/// ```
+#[macro_export]
+macro_rules! uri_ref {
+    ($s:expr) => {{
+        const URI_REF: $crate::UriRef<&'static str> = $crate::UriRef::parse_or_panic($s);
+        URI_REF
+    }};
+}
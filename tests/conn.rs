@@ -0,0 +1,120 @@
+use fluent_uri::{component::Host, conn::ConnectionString, Uri};
+
+fn hosts(s: &str) -> Vec<(String, Option<String>)> {
+    Uri::parse(s)
+        .unwrap()
+        .authority()
+        .unwrap()
+        .hosts()
+        .map(|h| {
+            let h = h.unwrap();
+            (h.host().to_owned(), h.port().map(|p| p.as_str().to_owned()))
+        })
+        .collect()
+}
+
+#[test]
+fn authority_hosts() {
+    let host = |host: &str, port: Option<&str>| (host.to_owned(), port.map(str::to_owned));
+
+    assert_eq!(
+        hosts("postgresql://h1,h2/db"),
+        [host("h1", None), host("h2", None)]
+    );
+    assert_eq!(
+        hosts("postgresql://u:p@h1,h2:5433/db"),
+        [host("h1", None), host("h2", Some("5433"))]
+    );
+    assert_eq!(
+        hosts("http://example.com:80/"),
+        [host("example.com", Some("80"))]
+    );
+    assert_eq!(hosts("http://[::1]:80/"), [host("[::1]", Some("80"))]);
+
+    let uri = Uri::parse("postgresql://h1,,h2/").unwrap();
+    let e = uri
+        .authority()
+        .unwrap()
+        .hosts()
+        .nth(1)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 3");
+
+    let uri = Uri::parse("postgresql://h1,/").unwrap();
+    let mut iter = uri.authority().unwrap().hosts();
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn parse() {
+    let conn = ConnectionString::parse(
+        "mongodb+srv://us%40er:p%3Ass@[::1]:27017,10.0.0.1:27018,db.example.com,[v7.a,b]:1/my%20db?replicaSet=rs0&&ssl&w=1#frag",
+    )
+    .unwrap();
+    assert_eq!(conn.scheme().as_str(), "mongodb+srv");
+    assert_eq!(conn.userinfo().unwrap(), "us%40er:p%3Ass");
+    assert_eq!(conn.username().unwrap(), "us%40er");
+    assert_eq!(conn.password().unwrap(), "p%3Ass");
+
+    let hosts = conn.hosts();
+    assert_eq!(hosts.len(), 4);
+    #[cfg(feature = "net")]
+    assert!(matches!(hosts[0].host_parsed(), Host::Ipv6(..)));
+    assert_eq!(hosts[0].port_to_u16(), Ok(Some(27017)));
+    #[cfg(feature = "net")]
+    assert!(matches!(hosts[1].host_parsed(), Host::Ipv4(_)));
+    assert!(matches!(hosts[2].host_parsed(), Host::RegName(_)));
+    assert_eq!(hosts[2].port(), None);
+    assert_eq!(hosts[3].host(), "[v7.a,b]");
+
+    assert_eq!(conn.path(), "/my%20db");
+    assert_eq!(
+        conn.database().unwrap().decode().into_string().unwrap(),
+        "my db"
+    );
+    assert_eq!(conn.query().unwrap(), "replicaSet=rs0&&ssl&w=1");
+    let options: Vec<_> = conn
+        .options()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    assert_eq!(options, [("replicaSet", "rs0"), ("ssl", ""), ("w", "1")]);
+    assert_eq!(conn.option("ssl").unwrap(), "");
+    assert_eq!(conn.option("replicaset"), None);
+
+    let conn = ConnectionString::parse("postgresql:///?host=/var/run").unwrap();
+    assert!(conn.hosts().is_empty());
+    assert_eq!(conn.database(), None);
+    assert_eq!(conn.option("host").unwrap(), "/var/run");
+
+    let conn = ConnectionString::parse("redis://:secret@cache").unwrap();
+    assert_eq!(conn.username().unwrap(), "");
+    assert_eq!(conn.hosts()[0].host(), "cache");
+    assert_eq!(conn.path(), "");
+    assert_eq!(conn.database(), None);
+    assert_eq!(conn.options().count(), 0);
+}
+
+#[test]
+fn parse_error() {
+    let index = |s| -> usize {
+        let e = ConnectionString::parse(s).unwrap_err().to_string();
+        e.rsplit(' ').next().unwrap().parse().unwrap()
+    };
+
+    assert_eq!(index("mongodb"), 7);
+    assert_eq!(index("1db://h"), 0);
+    assert_eq!(index("db:/h"), 4);
+    assert_eq!(index("db:h"), 3);
+    assert_eq!(index("db:/"), 4);
+    assert_eq!(index("db:"), 3);
+    assert_eq!(index("db://u u@h"), 6);
+    assert_eq!(index("db://h1,h 2"), 9);
+    assert_eq!(index("db://h1,,h2"), 8);
+    assert_eq!(index("db://h1,h2:x"), 11);
+    assert_eq!(index("db://h1,[::1/"), 12);
+    assert_eq!(index("db://h/a b"), 8);
+    assert_eq!(index("db://h/?a b"), 9);
+}
@@ -0,0 +1,267 @@
+use fluent_uri::{
+    csp::{SourceExpression, SourceList},
+    origin::Origin,
+    Uri,
+};
+
+fn matches(expr: &str, uri: &str, origin: &str) -> bool {
+    let expr = SourceExpression::parse(expr).unwrap();
+    let origin = Origin::parse(origin).unwrap();
+    expr.matches(&Uri::parse(uri).unwrap(), &origin)
+}
+
+#[test]
+fn parse() {
+    assert!(matches!(
+        SourceExpression::parse("https:"),
+        Some(SourceExpression::Scheme(s)) if s.as_str() == "https"
+    ));
+    assert!(matches!(
+        SourceExpression::parse("'SELF'"),
+        Some(SourceExpression::SelfOrigin)
+    ));
+
+    let Some(SourceExpression::Host(expr)) =
+        SourceExpression::parse("https://*.cdn.example:*/static/")
+    else {
+        panic!("not a host-source");
+    };
+    assert_eq!(expr.scheme().unwrap().as_str(), "https");
+    assert_eq!(expr.host(), "*.cdn.example");
+    assert_eq!(expr.port(), Some("*"));
+    assert_eq!(expr.path().unwrap(), "/static/");
+
+    let Some(SourceExpression::Host(expr)) = SourceExpression::parse("example.com:8080") else {
+        panic!("not a host-source");
+    };
+    assert!(expr.scheme().is_none());
+    assert_eq!(expr.host(), "example.com");
+    assert_eq!(expr.port(), Some("8080"));
+    assert!(expr.path().is_none());
+
+    for s in [
+        "*",
+        "example.com.",
+        "a-b.c",
+        "*.example.com",
+        "wss://*",
+        "x:1/",
+    ] {
+        assert!(SourceExpression::parse(s).is_some(), "{s}");
+    }
+    for s in [
+        "",
+        "'none'",
+        "'unsafe-inline'",
+        "'nonce-abc'",
+        "'sha256-abc'",
+        "*.",
+        "**.example.com",
+        "a.*.example.com",
+        "a..b",
+        "ex_ample.com",
+        "example.com:8x",
+        "example.com/a b",
+        "example.com/%zz",
+        "1http://example.com",
+        "[::1]",
+        ":",
+    ] {
+        assert!(SourceExpression::parse(s).is_none(), "{s}");
+    }
+}
+
+#[test]
+fn scheme_source() {
+    let origin = "https://example.com";
+    assert!(matches("https:", "https://a.example/", origin));
+    assert!(matches("HTTPS:", "https://a.example/", origin));
+    assert!(matches("http:", "https://a.example/", origin));
+    assert!(!matches("https:", "http://a.example/", origin));
+    assert!(matches("ws:", "wss://a.example/", origin));
+    assert!(matches("ws:", "https://a.example/", origin));
+    assert!(matches("wss:", "https://a.example/", origin));
+    assert!(!matches("wss:", "ws://a.example/", origin));
+    assert!(matches("data:", "data:text/plain,hi", origin));
+    assert!(matches("blob:", "blob:https://example.com/uuid", origin));
+    assert!(!matches("data:", "blob:https://example.com/uuid", origin));
+}
+
+#[test]
+fn star() {
+    let origin = "https://example.com";
+    assert!(matches("*", "https://a.example/", origin));
+    assert!(matches("*", "http://a.example/", origin));
+    assert!(!matches("*", "data:text/plain,hi", origin));
+    assert!(!matches("*", "ftp://a.example/", origin));
+    assert!(matches("*", "ftp://a.example/", "ftp://example.com"));
+    assert!(!matches("*", "ftp://a.example/", "null"));
+    assert!(matches("https://*", "https://a.example/", origin));
+    assert!(!matches("https://*", "https://a.example:1/", origin));
+    assert!(matches("https://*:*", "https://a.example:1/", origin));
+}
+
+#[test]
+fn host_source() {
+    let origin = "https://example.com";
+
+    // Schemeless expressions inherit the scheme of the origin.
+    assert!(matches("cdn.example", "https://cdn.example/", origin));
+    assert!(matches("CDN.Example", "https://cdn.example/", origin));
+    assert!(!matches("cdn.example", "http://cdn.example/", origin));
+    assert!(matches(
+        "cdn.example",
+        "https://cdn.example/",
+        "http://example.com"
+    ));
+    assert!(matches(
+        "cdn.example",
+        "http://cdn.example/",
+        "http://example.com"
+    ));
+    assert!(!matches("cdn.example", "https://cdn.example/", "null"));
+
+    // Upgrades from insecure schemes.
+    assert!(matches(
+        "http://cdn.example",
+        "https://cdn.example/",
+        origin
+    ));
+    assert!(!matches(
+        "https://cdn.example",
+        "http://cdn.example/",
+        origin
+    ));
+
+    // Wildcard hosts.
+    assert!(matches("*.cdn.example", "https://a.cdn.example/", origin));
+    assert!(matches("*.cdn.example", "https://a.b.CDN.example/", origin));
+    assert!(!matches("*.cdn.example", "https://cdn.example/", origin));
+    assert!(!matches(
+        "*.cdn.example",
+        "https://evilcdn.example/",
+        origin
+    ));
+    assert!(!matches("*.0.0.1", "https://127.0.0.1/", origin));
+
+    // IP addresses.
+    assert!(matches("127.0.0.1", "https://127.0.0.1/", origin));
+    assert!(!matches("127.0.0.1", "https://127.0.0.2/", origin));
+
+    // No host.
+    assert!(matches("*", "file://a/etc/passwd", "file://host"));
+    assert!(!matches("file://*", "file:///etc/passwd", origin));
+    assert!(!matches("https://*", "https:/path", origin));
+}
+
+#[test]
+fn port_part() {
+    let origin = "https://example.com";
+    assert!(matches("cdn.example", "https://cdn.example:443/", origin));
+    assert!(!matches("cdn.example", "https://cdn.example:8443/", origin));
+    assert!(matches(
+        "cdn.example:8443",
+        "https://cdn.example:8443/",
+        origin
+    ));
+    assert!(!matches("cdn.example:8443", "https://cdn.example/", origin));
+    assert!(matches("cdn.example:443", "https://cdn.example/", origin));
+    assert!(matches("cdn.example:*", "https://cdn.example:1/", origin));
+    assert!(matches("cdn.example:*", "https://cdn.example/", origin));
+    // The default port of the upgraded scheme is allowed.
+    assert!(matches(
+        "http://cdn.example",
+        "https://cdn.example/",
+        origin
+    ));
+    assert!(matches(
+        "http://cdn.example:80",
+        "http://cdn.example/",
+        origin
+    ));
+    assert!(!matches(
+        "http://cdn.example:80",
+        "https://cdn.example/",
+        origin
+    ));
+}
+
+#[test]
+fn path_part() {
+    let origin = "https://example.com";
+    let cases = [
+        ("cdn.example/", "https://cdn.example", true),
+        ("cdn.example/", "https://cdn.example/a/b", true),
+        ("cdn.example/static/", "https://cdn.example/static/", true),
+        (
+            "cdn.example/static/",
+            "https://cdn.example/static/a/b.js",
+            true,
+        ),
+        ("cdn.example/static/", "https://cdn.example/static", false),
+        (
+            "cdn.example/static/",
+            "https://cdn.example/staticx/a.js",
+            false,
+        ),
+        ("cdn.example/app.js", "https://cdn.example/app.js", true),
+        ("cdn.example/app.js", "https://cdn.example/app.js?v=1", true),
+        ("cdn.example/app.js", "https://cdn.example/app.js/", false),
+        (
+            "cdn.example/app.js",
+            "https://cdn.example/static/app.js",
+            false,
+        ),
+        ("cdn.example/a%20b/", "https://cdn.example/a%20b/c", true),
+        ("cdn.example/%61pp.js", "https://cdn.example/app.js", true),
+        ("cdn.example/a/", "https://cdn.example/a%2Fb", false),
+    ];
+    for (expr, uri, expected) in cases {
+        assert_eq!(matches(expr, uri, origin), expected, "{expr} {uri}");
+    }
+}
+
+#[test]
+fn self_origin() {
+    let origin = "http://example.com";
+    assert!(matches("'self'", "http://example.com/a", origin));
+    assert!(matches("'self'", "HTTP://EXAMPLE.com:80/a", origin));
+    assert!(matches("'self'", "https://example.com/a", origin));
+    assert!(matches("'self'", "ws://example.com/a", origin));
+    assert!(matches("'self'", "wss://example.com/a", origin));
+    assert!(!matches("'self'", "http://example.com:8080/a", origin));
+    assert!(!matches("'self'", "http://a.example.com/", origin));
+
+    let origin = "https://example.com";
+    assert!(matches("'self'", "https://example.com/a", origin));
+    assert!(matches("'self'", "wss://example.com/a", origin));
+    assert!(!matches("'self'", "http://example.com/a", origin));
+    assert!(!matches("'self'", "ws://example.com/a", origin));
+
+    let origin = "https://example.com:8443";
+    assert!(matches("'self'", "https://example.com:8443/", origin));
+    assert!(matches("'self'", "wss://example.com:8443/", origin));
+    assert!(!matches("'self'", "https://example.com/", origin));
+
+    assert!(!matches("'self'", "data:text/plain,hi", "null"));
+    assert!(!matches("'self'", "https://example.com/", "null"));
+}
+
+#[test]
+fn source_list() {
+    let origin = Origin::parse("https://example.com").unwrap();
+    let list = SourceList::parse(" 'self'\thttps://*.cdn.example/js/ blob:  'nonce-abc' 'none' ");
+    assert_eq!(list.expressions().len(), 3);
+
+    let matches = |s| list.matches(&Uri::parse(s).unwrap(), &origin);
+    assert!(matches("https://example.com/app.js"));
+    assert!(matches("https://a.cdn.example/js/app.js"));
+    assert!(matches("blob:https://example.com/uuid"));
+    assert!(!matches("https://a.cdn.example/css/app.css"));
+    assert!(!matches("https://evil.example/"));
+
+    let list = SourceList::parse("'none'");
+    assert!(list.expressions().is_empty());
+    assert!(!list.matches(&Uri::parse("https://example.com/").unwrap(), &origin));
+    assert!(SourceList::default().expressions().is_empty());
+}
@@ -0,0 +1,231 @@
+use fluent_uri::{
+    component::Host,
+    dns::{DnsNameOptions, HostSet},
+    Uri,
+};
+
+fn check(host: &str) -> Result<String, String> {
+    Host::parse(host)
+        .unwrap()
+        .as_dns_name()
+        .map(|name| name.into_string())
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn valid() {
+    assert_eq!(check("example.com").unwrap(), "example.com");
+    assert_eq!(check("Example.COM.").unwrap(), "Example.COM");
+    assert_eq!(check("localhost").unwrap(), "localhost");
+    assert_eq!(check("a-b.c0").unwrap(), "a-b.c0");
+    assert_eq!(check("0a.example").unwrap(), "0a.example");
+    assert_eq!(check("123.example").unwrap(), "123.example");
+    assert_eq!(check("%65xample.com").unwrap(), "example.com");
+    assert_eq!(check("ex%2Dample.com%2E").unwrap(), "ex-ample.com");
+    assert_eq!(
+        check("xn--bcher-kva.example").unwrap(),
+        "xn--bcher-kva.example"
+    );
+
+    let label = "a".repeat(63);
+    assert!(check(&format!("{label}.com")).is_ok());
+
+    // 3 * 64 + 61 = 253 octets.
+    let name = format!("{label}.{label}.{label}.{}", "b".repeat(61));
+    assert_eq!(name.len(), 253);
+    assert!(check(&name).is_ok());
+    assert!(check(&format!("{name}.")).is_ok());
+}
+
+#[test]
+fn invalid() {
+    let cases = [
+        ("", "empty DNS name"),
+        (".", "empty DNS name"),
+        ("a..b", "empty label in DNS name"),
+        (".a", "empty label in DNS name"),
+        ("a.b..", "empty label in DNS name"),
+        ("_x_", "invalid character in DNS name"),
+        ("%00", "invalid character in DNS name"),
+        ("a%2Fb", "invalid character in DNS name"),
+        ("b%C3%BCcher.example", "invalid character in DNS name"),
+        ("a%20b", "invalid character in DNS name"),
+        ("a,b", "invalid character in DNS name"),
+        ("-a.com", "label starting or ending with '-' in DNS name"),
+        ("a-.com", "label starting or ending with '-' in DNS name"),
+        ("1.2.3", "all-numeric last label in DNS name"),
+        ("example.123", "all-numeric last label in DNS name"),
+        ("0x7f.1", "all-numeric last label in DNS name"),
+        ("127.0.0.1", "host is not a registered name"),
+        ("[::1]", "host is not a registered name"),
+        ("[v1.x]", "host is not a registered name"),
+    ];
+    for (host, msg) in cases {
+        assert_eq!(check(host).unwrap_err(), msg, "{host}");
+    }
+
+    let label = "a".repeat(64);
+    assert_eq!(
+        check(&format!("{label}.com")).unwrap_err(),
+        "label longer than 63 octets in DNS name"
+    );
+
+    let name = "a.".repeat(127);
+    assert_eq!(name.len(), 254);
+    assert_eq!(
+        check(&format!("{name}a")).unwrap_err(),
+        "DNS name longer than 253 octets"
+    );
+    assert_eq!(
+        check(&"a".repeat(300)).unwrap_err(),
+        "DNS name longer than 253 octets"
+    );
+}
+
+#[test]
+fn underscore() {
+    let host = Host::parse("_sip._udp.Example.com").unwrap();
+    assert!(host.as_dns_name().is_err());
+
+    let options = DnsNameOptions::new().allow_underscore(true);
+    let name = host.as_dns_name_with(options).unwrap();
+    assert!(!name.is_fqdn());
+    assert!(name.labels().eq(["_sip", "_udp", "Example", "com"]));
+    assert!(name.labels().rev().eq(["com", "Example", "_udp", "_sip"]));
+
+    let host = Host::parse("-_.com").unwrap();
+    assert!(host.as_dns_name_with(options).is_err());
+}
+
+#[test]
+fn from_uri() {
+    let uri = Uri::parse("http://user@API.example.com.:8080/").unwrap();
+    let host = uri.authority().unwrap().host_parsed();
+    let name = host.as_dns_name().unwrap();
+    assert_eq!(name.as_str(), "API.example.com");
+    assert!(name.is_fqdn());
+    assert_eq!(name.labels().count(), 3);
+
+    let uri = Uri::parse("http://a%2E%2Eb/").unwrap();
+    let host = uri.authority().unwrap().host_parsed();
+    assert!(host.as_dns_name().is_err());
+}
+
+#[test]
+fn subdomain() {
+    let is_subdomain = |host, domain| Host::parse(host).unwrap().is_subdomain_of(domain);
+
+    assert!(is_subdomain("example.com", "example.com"));
+    assert!(is_subdomain("www.example.com", "example.com"));
+    assert!(is_subdomain("a.b.EXAMPLE.com.", "Example.COM"));
+    assert!(is_subdomain("www.example.com", "example.com."));
+    assert!(is_subdomain("www.%65xample.com", "example.com"));
+    assert!(is_subdomain("www.example.com", "com"));
+
+    assert!(!is_subdomain("badexample.com", "example.com"));
+    assert!(!is_subdomain("example.com.evil.net", "example.com"));
+    assert!(!is_subdomain("example.com", "www.example.com"));
+    assert!(!is_subdomain("example.com", ""));
+    assert!(!is_subdomain("example.com", "."));
+    assert!(!is_subdomain("", "example.com"));
+    assert!(!is_subdomain("127.0.0.1", "0.0.1"));
+    assert!(!is_subdomain("[::1]", "1]"));
+
+    // Names that a resolver may truncate or reinterpret never match.
+    assert!(!is_subdomain("evil.net%00.example.com", "example.com"));
+    assert!(!is_subdomain("evil.net%2F.example.com", "example.com"));
+    assert!(!is_subdomain("evil.net%FF.example.com", "example.com"));
+    assert!(!is_subdomain("b%C3%BCcher.example.com", "example.com"));
+    assert!(!is_subdomain("a..example.com", "example.com"));
+    assert!(!is_subdomain("www.example.com", "example.com/"));
+}
+
+#[test]
+fn wildcard() {
+    let matches = |host, pattern| Host::parse(host).unwrap().matches_wildcard(pattern);
+
+    assert!(matches("www.example.com", "*.example.com"));
+    assert!(matches("WWW.Example.COM.", "*.example.com."));
+    assert!(matches("x.example.com", "*.EXAMPLE.com"));
+    assert!(matches("example.com", "example.com"));
+    assert!(matches("Example.com", "example.com."));
+
+    assert!(!matches("example.com", "*.example.com"));
+    assert!(!matches(".example.com", "*.example.com"));
+    assert!(!matches("a.b.example.com", "*.example.com"));
+    assert!(!matches("www.example.com.evil.net", "*.example.com"));
+    assert!(!matches("www.example.com", "w*.example.com"));
+    assert!(!matches("www.example.com", "*w.example.com"));
+    assert!(!matches("www.example.com", "www.*.com"));
+    assert!(!matches("www.example.com", "*.*.com"));
+    assert!(!matches("www.example.com", "*"));
+    assert!(!matches("*.example.com", "www.example.com"));
+    assert!(!matches("www.example.com", "example.com"));
+    assert!(!matches("127.0.0.1", "*.0.0.1"));
+
+    assert!(!matches("evil%00.example.com", "*.example.com"));
+    assert!(!matches("evil%2F.example.com", "*.example.com"));
+    assert!(!matches("evil%C3.example.com", "*.example.com"));
+    assert!(!matches("www.example.com%00", "www.example.com"));
+}
+
+#[test]
+fn host_set() {
+    let mut set = HostSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert("example.com").unwrap());
+    assert!(!set.insert("EXAMPLE.com.").unwrap());
+    assert!(set.insert("*.cdn.example.net").unwrap());
+    assert!(set.insert(".Internal.example.org").unwrap());
+    assert_eq!(set.len(), 3);
+
+    let contains = |host| set.contains(Host::parse(host).unwrap());
+
+    assert!(contains("example.com"));
+    assert!(contains("Example.COM."));
+    assert!(!contains("www.example.com"));
+    assert!(!contains("example.com.evil.net"));
+
+    assert!(contains("a.cdn.example.net"));
+    assert!(contains("A.CDN.example.net."));
+    assert!(!contains("cdn.example.net"));
+    assert!(!contains("a.b.cdn.example.net"));
+    assert!(!contains(".cdn.example.net"));
+
+    assert!(contains("internal.example.org"));
+    assert!(contains("db.internal.example.org"));
+    assert!(contains("x.y.z.internal.example.org"));
+    assert!(!contains("notinternal.example.org"));
+    assert!(!contains("example.org"));
+
+    assert!(!contains("127.0.0.1"));
+    assert!(!contains(""));
+    assert!(!contains("example.com%00"));
+    assert!(!contains("a%00.cdn.example.net"));
+    assert!(!contains("evil.net%2F.internal.example.org"));
+    assert!(!contains("%FF.internal.example.org"));
+
+    let patterns: Vec<_> = (0..5000).map(|i| format!("host{i}.example.com")).collect();
+    let set = HostSet::from_patterns(patterns.iter().map(String::as_str)).unwrap();
+    assert_eq!(set.len(), 5000);
+    assert!(set.contains(Host::parse("host4999.example.com").unwrap()));
+    assert!(!set.contains(Host::parse("host5000.example.com").unwrap()));
+}
+
+#[test]
+fn host_set_invalid() {
+    let err = |pattern| HostSet::new().insert(pattern).unwrap_err().to_string();
+    assert_eq!(err("f*.example.com"), "invalid wildcard in host pattern");
+    assert_eq!(err("*.*.example.com"), "invalid wildcard in host pattern");
+    assert_eq!(err("*"), "invalid wildcard in host pattern");
+    assert_eq!(err("127.0.0.1"), "host is not a registered name");
+    assert_eq!(err("[::1]"), "host is not a registered name");
+    assert_eq!(err(""), "empty DNS name");
+    assert_eq!(err("*."), "empty DNS name");
+    assert_eq!(err("a b"), "invalid character in DNS name");
+    assert_eq!(err("a%00.example.com"), "invalid character in DNS name");
+    assert_eq!(err("%FF.example.com"), "invalid character in DNS name");
+    assert_eq!(err(".a..b"), "empty label in DNS name");
+
+    assert!(HostSet::from_patterns(["example.com", "a/b"]).is_err());
+}
@@ -0,0 +1,156 @@
+use fluent_uri::Uri;
+
+#[test]
+fn unix_path() {
+    let from = |s: &[u8]| Uri::from_unix_path(s).unwrap();
+    assert_eq!(from(b"/"), "file:///");
+    assert_eq!(from(b"/etc/hosts"), "file:///etc/hosts");
+    assert_eq!(from(b"/a b/c#d?e%f"), "file:///a%20b/c%23d%3Fe%25f");
+    assert_eq!(from(b"/a:b@c;d=e,f"), "file:///a:b@c;d=e,f");
+    assert_eq!(from(b"//a/../b"), "file:////a/../b");
+    assert_eq!(from("/é".as_bytes()), "file:///%C3%A9");
+    assert_eq!(from(b"/\xFE\xFF"), "file:///%FE%FF");
+
+    let err = |s: &[u8]| Uri::from_unix_path(s).unwrap_err().to_string();
+    assert_eq!(err(b""), "file path is not absolute");
+    assert_eq!(err(b"a/b"), "file path is not absolute");
+
+    let to = |s| Uri::parse(s).unwrap().to_unix_path().unwrap();
+    assert_eq!(to("file:///etc/hosts"), b"/etc/hosts");
+    assert_eq!(to("FILE://LocalHost/etc/hosts"), b"/etc/hosts");
+    assert_eq!(to("file:/etc/hosts"), b"/etc/hosts");
+    assert_eq!(to("file:///a%20b?q#f"), b"/a b");
+    assert_eq!(to("file:///%FE%2F"), b"/\xFE/");
+
+    let err = |s| {
+        Uri::parse(s)
+            .unwrap()
+            .to_unix_path()
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(err("http://localhost/a"), "scheme other than \"file\"");
+    assert_eq!(
+        err("file://u@localhost/a"),
+        "file URI with userinfo or port"
+    );
+    assert_eq!(
+        err("file://localhost:1/a"),
+        "file URI with userinfo or port"
+    );
+    assert_eq!(err("file://server/a"), "file URI with non-local host");
+    assert_eq!(err("file://127.0.0.1/a"), "file URI with non-local host");
+    assert_eq!(err("file://localhost"), "file path is not absolute");
+    assert_eq!(err("file:a/b"), "file path is not absolute");
+
+    for path in [&b"/a b/#?%/\x00\xFF"[..], b"/%41", b"/./..//x/"] {
+        let uri = Uri::from_unix_path(path).unwrap();
+        assert_eq!(uri.to_unix_path().unwrap(), path);
+    }
+}
+
+#[test]
+fn windows_path() {
+    let from = |s| Uri::from_windows_path(s).unwrap();
+    assert_eq!(from(r"C:\"), "file:///C:/");
+    assert_eq!(from(r"C:"), "file:///C:/");
+    assert_eq!(from(r"c:\a\b.txt"), "file:///c:/a/b.txt");
+    assert_eq!(from(r"D:/a/b"), "file:///D:/a/b");
+    assert_eq!(
+        from(r"C:\Program Files\a#b\100%.txt"),
+        "file:///C:/Program%20Files/a%23b/100%25.txt"
+    );
+    assert_eq!(from(r"C:\ü"), "file:///C:/%C3%BC");
+    assert_eq!(from(r"\\server\share"), "file://server/share");
+    assert_eq!(from(r"\\server\share\a b"), "file://server/share/a%20b");
+    assert_eq!(from(r"//server/share/"), "file://server/share/");
+    assert_eq!(from(r"\\?\C:\a"), "file:///C:/a");
+    assert_eq!(from(r"\\?\UNC\server\share\a"), "file://server/share/a");
+    assert_eq!(from(r"\\my server\share"), "file://my%20server/share");
+
+    let err = |s| Uri::from_windows_path(s).unwrap_err().to_string();
+    assert_eq!(err(r"a\b"), "file path is not absolute");
+    assert_eq!(err(r"\a\b"), "file path is not absolute");
+    assert_eq!(err(r"C:a"), "file path is not absolute");
+    assert_eq!(err(r"1:\a"), "file path is not absolute");
+    assert_eq!(err(r"\\server"), "UNC path without server or share name");
+    assert_eq!(err(r"\\server\"), "UNC path without server or share name");
+    assert_eq!(err(r"\\\share"), "UNC path without server or share name");
+
+    let to = |s| Uri::parse(s).unwrap().to_windows_path().unwrap();
+    assert_eq!(to("file:///C:/"), r"C:\");
+    assert_eq!(to("file:///C:"), r"C:\");
+    assert_eq!(to("file:///c:/a/b.txt"), r"c:\a\b.txt");
+    assert_eq!(to("file:///C%7C/a"), r"C:\a");
+    assert_eq!(to("file://localhost/C:/a"), r"C:\a");
+    assert_eq!(to("file:/C:/a"), r"C:\a");
+    assert_eq!(to("file:///C:/a%20b/%23.txt?q#f"), r"C:\a b\#.txt");
+    assert_eq!(to("file:///C:/%C3%BC"), r"C:\ü");
+    assert_eq!(to("file://server/share/a"), r"\\server\share\a");
+    assert_eq!(to("file://my%20server/share"), r"\\my server\share");
+    assert_eq!(to("file://192.168.0.1/share"), r"\\192.168.0.1\share");
+    assert_eq!(to("file:////server/share/a"), r"\\server\share\a");
+
+    let err = |s| {
+        Uri::parse(s)
+            .unwrap()
+            .to_windows_path()
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(err("http:///C:/"), "scheme other than \"file\"");
+    assert_eq!(
+        err("file://u@server/share"),
+        "file URI with userinfo or port"
+    );
+    assert_eq!(err("file:///etc/hosts"), "file path is not absolute");
+    assert_eq!(err("file:///C:a"), "file path is not absolute");
+    assert_eq!(err("file:C:/a"), "file path is not absolute");
+    assert_eq!(err("file:///C:/%FF"), "invalid UTF-8 in file path");
+    assert_eq!(err("file://%FF/share"), "invalid UTF-8 in file path");
+    assert_eq!(
+        err("file://server"),
+        "UNC path without server or share name"
+    );
+    assert_eq!(
+        err("file://server/"),
+        "UNC path without server or share name"
+    );
+    assert_eq!(
+        err("file://[::1]/share"),
+        "UNC path without server or share name"
+    );
+    assert_eq!(
+        err("file:////server"),
+        "UNC path without server or share name"
+    );
+
+    for path in [r"C:\a b\#?%\ü", r"\\server\share\x y", r"Z:\"] {
+        let uri = Uri::from_windows_path(path).unwrap();
+        assert_eq!(uri.to_windows_path().unwrap(), path);
+    }
+}
+
+#[cfg(all(feature = "std", unix))]
+#[test]
+fn file_path() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+    let uri = Uri::from_file_path("/home/user/a b#1.rs").unwrap();
+    assert_eq!(uri, "file:///home/user/a%20b%231.rs");
+    assert_eq!(
+        uri.to_file_path().unwrap(),
+        Path::new("/home/user/a b#1.rs")
+    );
+
+    let path = Path::new(OsStr::from_bytes(b"/tmp/\xFF\xFE"));
+    let uri = Uri::from_file_path(path).unwrap();
+    assert_eq!(uri, "file:///tmp/%FF%FE");
+    assert_eq!(uri.to_file_path().unwrap(), path);
+
+    assert!(Uri::from_file_path("rel/path").is_err());
+    assert!(Uri::parse("file://server/a")
+        .unwrap()
+        .to_file_path()
+        .is_err());
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "idna")]
+
+use fluent_uri::{
+    component::Host,
+    encoding::{encoder::RegName, EStr},
+    punycode, Uri, UriRef,
+};
+
+#[test]
+fn to_ascii() {
+    let name = EStr::<RegName>::new_or_panic("M%C3%BCnchen.DE");
+    assert_eq!(name.to_ascii().unwrap(), "xn--mnchen-3ya.de");
+
+    let name = EStr::<RegName>::new_or_panic("example.com");
+    assert_eq!(name.to_ascii().unwrap(), "example.com");
+
+    // Fullwidth full stop is mapped to a dot.
+    let name = EStr::<RegName>::new_or_panic("a%EF%BC%8Eb");
+    assert_eq!(name.to_ascii().unwrap(), "a.b");
+
+    let name = EStr::<RegName>::new_or_panic("%FF.com");
+    let e = name.to_ascii().unwrap_err();
+    assert_eq!(e.to_string(), "invalid UTF-8 in registered name");
+
+    let name = EStr::<RegName>::new_or_panic("xn--a.com");
+    let e = name.to_ascii().unwrap_err();
+    assert_eq!(e.to_string(), "invalid internationalized domain name");
+}
+
+#[test]
+fn to_unicode() {
+    let name = EStr::<RegName>::new_or_panic("xn--mnchen-3ya.de");
+    assert_eq!(name.to_unicode().unwrap(), "münchen.de");
+
+    let name = EStr::<RegName>::new_or_panic("EXAMPLE.com");
+    assert_eq!(name.to_unicode().unwrap(), "example.com");
+
+    assert!(EStr::<RegName>::new_or_panic("%C3").to_unicode().is_err());
+    assert!(EStr::<RegName>::new_or_panic("xn--a").to_unicode().is_err());
+}
+
+#[test]
+fn punycode() {
+    let cases = [
+        ("", ""),
+        ("a", "a-"),
+        ("ü", "tda"),
+        ("münchen", "mnchen-3ya"),
+        ("例え", "r8jz45g"),
+    ];
+    for (unicode, encoded) in cases {
+        assert_eq!(punycode::encode(unicode).unwrap(), encoded);
+        assert_eq!(punycode::decode(encoded).unwrap(), unicode);
+    }
+}
+
+#[test]
+fn normalize_idna() {
+    let uri = Uri::parse("HTTP://user@M%C3%BCnchen.DE:80/a/../b?q#f").unwrap();
+    let normalized = uri.normalize_idna().unwrap();
+    assert_eq!(normalized, "http://user@xn--mnchen-3ya.de:80/b?q#f");
+    let auth = normalized.authority().unwrap();
+    assert_eq!(auth.host(), "xn--mnchen-3ya.de");
+    assert_eq!(auth.port().unwrap(), "80");
+    assert_eq!(normalized.path(), "/b");
+    assert_eq!(normalized.query().unwrap(), "q");
+    assert_eq!(normalized.fragment().unwrap(), "f");
+    assert_eq!(normalized.normalize_idna().unwrap(), normalized);
+
+    // ASCII hosts are left as is.
+    let uri = UriRef::parse("//%41%2A.com/").unwrap();
+    assert_eq!(uri.normalize_idna().unwrap(), uri.normalize());
+    let uri = Uri::parse("http://[::1]/").unwrap();
+    assert_eq!(uri.normalize_idna().unwrap(), "http://[::1]/");
+
+    // Fullwidth digits are mapped to an IPv4 address.
+    let uri = Uri::parse("http://%EF%BC%91.2.3.4/").unwrap();
+    let normalized = uri.normalize_idna().unwrap();
+    assert_eq!(normalized, "http://1.2.3.4/");
+    assert!(matches!(
+        normalized.authority().unwrap().host_parsed(),
+        Host::Ipv4 { .. }
+    ));
+
+    assert!(Uri::parse("http://%FF/").unwrap().normalize_idna().is_err());
+}
@@ -0,0 +1,106 @@
+use fluent_uri::{
+    map::{OffsetMap, Source},
+    Uri, UriRef,
+};
+
+// Checks that every byte mapped one-to-one is equal to its source byte ignoring case.
+fn check(out: &str, map: &OffsetMap, input: &str, base: &str) {
+    assert_eq!(map.len(), out.len());
+    for (i, x) in out.bytes().enumerate() {
+        let Some((src, range)) = map.map(i) else {
+            continue;
+        };
+        let src = match src {
+            Source::Input => input,
+            Source::Base => base,
+        };
+        if range.len() == 1 {
+            assert!(x.eq_ignore_ascii_case(&src.as_bytes()[range.start]));
+        }
+    }
+}
+
+#[test]
+fn normalize_with_map() {
+    for s in [
+        "eXAMPLE://a/./b/../b/%63/%7bfoo%7d",
+        "HTTP://us%65r@www.EXAMPLE.com:/%2E/a?%61%3a#%7E",
+        "http://[0:0::1]:80/",
+        "a:/.//@@",
+        "foo/../bar",
+        "",
+    ] {
+        let (r, map) = UriRef::parse(s).unwrap().normalize_with_map();
+        assert_eq!(r, UriRef::parse(s).unwrap().normalize());
+        check(r.as_str(), &map, s, "");
+    }
+
+    let (r, map) = UriRef::parse("a:/.//@@").unwrap().normalize_with_map();
+    assert_eq!(r, "a:/.//@@");
+    assert_eq!(map.map(2), None);
+    assert_eq!(map.map(3), None);
+    assert_eq!(map.map(4), Some((Source::Input, 2..3)));
+    assert_eq!(map.map(5), Some((Source::Input, 5..6)));
+    assert_eq!(map.map(8), None);
+    assert_eq!(map.map_range(2..4), None);
+    assert_eq!(map.map_range(2..6), Some((Source::Input, 2..6)));
+
+    let (r, map) = UriRef::parse("http://%61%62/")
+        .unwrap()
+        .normalize_with_map();
+    assert_eq!(r, "http://ab/");
+    assert_eq!(map.map(7), Some((Source::Input, 7..10)));
+    assert_eq!(map.map(8), Some((Source::Input, 10..13)));
+    assert_eq!(map.map_range(7..9), Some((Source::Input, 7..13)));
+}
+
+#[test]
+fn resolve_against_with_map() {
+    let base_s = "http://a/b/c/d;p?q";
+    let base = Uri::parse(base_s).unwrap();
+
+    for s in [
+        "g:h",
+        "g",
+        "./g",
+        "g/",
+        "/g",
+        "//g",
+        "?y",
+        "g?y",
+        "#s",
+        "g#s",
+        "",
+        ".",
+        "..",
+        "../..",
+        "../../../g",
+        "/./g",
+        "g/./h",
+        "g;x=1/../y",
+    ] {
+        let r = UriRef::parse(s).unwrap();
+        let (t, map) = r.resolve_against_with_map(&base).unwrap();
+        assert_eq!(t, r.resolve_against(&base).unwrap());
+        check(t.as_str(), &map, s, base_s);
+    }
+
+    let (t, map) = UriRef::parse("../g?y#s")
+        .unwrap()
+        .resolve_against_with_map(&base)
+        .unwrap();
+    assert_eq!(t, "http://a/b/g?y#s");
+    assert_eq!(map.map_range(0..11), Some((Source::Base, 0..11)));
+    assert_eq!(map.map(11), Some((Source::Input, 3..4)));
+    assert_eq!(map.map_range(11..16), Some((Source::Input, 3..8)));
+    assert_eq!(map.map_range(10..12), None);
+
+    let base = Uri::parse("foo://h").unwrap();
+    let (t, map) = UriRef::parse("a")
+        .unwrap()
+        .resolve_against_with_map(&base)
+        .unwrap();
+    assert_eq!(t, "foo://h/a");
+    assert_eq!(map.map(7), None);
+    assert_eq!(map.map(8), Some((Source::Input, 0..1)));
+}
@@ -97,7 +97,7 @@ fn normalize() {
     #[cfg(feature = "net")]
     assert!(matches!(
         r.normalize().authority().unwrap().host_parsed(),
-        Host::Ipv6(Ipv6Addr::LOCALHOST)
+        Host::Ipv6(Ipv6Addr::LOCALHOST, None)
     ));
 
     // Verbose IPv6 address.
@@ -106,7 +106,7 @@ fn normalize() {
     #[cfg(feature = "net")]
     assert!(matches!(
         r.normalize().authority().unwrap().host_parsed(),
-        Host::Ipv6(Ipv6Addr::LOCALHOST)
+        Host::Ipv6(Ipv6Addr::LOCALHOST, None)
     ));
 
     // IPv4-mapped IPv6 address.
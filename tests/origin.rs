@@ -0,0 +1,155 @@
+use fluent_uri::{origin::Origin, Uri, UriRef};
+
+fn origin(s: &str) -> Origin {
+    Uri::parse(s).unwrap().origin()
+}
+
+#[test]
+fn tuple_origins() {
+    let Origin::Tuple(t) = origin("https://user@Example.COM/a?b#c") else {
+        panic!()
+    };
+    assert_eq!(t.scheme(), "https");
+    assert_eq!(t.host(), "example.com");
+    assert_eq!(t.port(), Some(443));
+
+    let Origin::Tuple(t) = origin("foo://example.com") else {
+        panic!()
+    };
+    assert_eq!(t.port(), None);
+
+    let Origin::Tuple(t) = origin("foo://example.com:1") else {
+        panic!()
+    };
+    assert_eq!(t.port(), Some(1));
+
+    assert!(origin("https://a").is_same_origin(&origin("https://a:443/")));
+    assert!(origin("https://a").is_same_origin(&origin("https://a:/")));
+    assert!(origin("http://a").is_same_origin(&origin("HTTP://%61/")));
+    assert!(origin("ws://a").is_same_origin(&origin("ws://a:80")));
+    assert!(origin("http://[::1]").is_same_origin(&origin("http://[0::1]:80")));
+
+    assert!(!origin("https://a").is_same_origin(&origin("http://a")));
+    assert!(!origin("https://a").is_same_origin(&origin("https://a:444")));
+    assert!(!origin("https://a").is_same_origin(&origin("https://b")));
+    assert!(!origin("https://a").is_same_origin(&origin("wss://a")));
+}
+
+#[test]
+fn opaque_origins() {
+    for s in [
+        "data:text/plain,hi",
+        "file:///etc/hosts",
+        "mailto:user@example.com",
+        "http://a:65536/",
+        "http:///path",
+    ] {
+        let o = origin(s);
+        assert!(matches!(o, Origin::Opaque), "{s}");
+        assert!(!o.is_same_origin(&o));
+        assert_eq!(o.serialize(), "null");
+    }
+}
+
+#[test]
+fn serialize() {
+    assert_eq!(
+        origin("HTTPS://Example.com:443/a").serialize(),
+        "https://example.com"
+    );
+    assert_eq!(
+        origin("http://example.com:443/").serialize(),
+        "http://example.com:443"
+    );
+    assert_eq!(
+        origin("http://127.0.0.1:8080").serialize(),
+        "http://127.0.0.1:8080"
+    );
+    assert_eq!(origin("ftp://[0::1]").serialize(), "ftp://[::1]");
+    assert_eq!(origin("foo://a:1").serialize(), "foo://a:1");
+    assert_eq!(origin("foo://a").to_string(), "foo://a");
+}
+
+#[test]
+fn parse() {
+    for s in [
+        "https://example.com",
+        "http://example.com:8080",
+        "foo://a",
+        "http://[::1]",
+    ] {
+        let o = Origin::parse(s).unwrap();
+        assert_eq!(o.serialize(), s);
+    }
+
+    let o = Origin::parse("HTTPS://EXAMPLE.com:443").unwrap();
+    assert_eq!(o.serialize(), "https://example.com");
+    assert!(o.is_same_origin(&origin("https://example.com/x")));
+
+    assert!(matches!(Origin::parse("null").unwrap(), Origin::Opaque));
+    assert!(Origin::parse("data:,x").is_err());
+
+    let cases = [
+        ("https://a/", "unexpected character at index 9"),
+        ("https://a?", "unexpected character at index 9"),
+        ("https://a#", "unexpected character at index 9"),
+        ("https://u@a", "unexpected character at index 9"),
+        ("mailto:a", "unexpected character at index 7"),
+        ("Null", "unexpected character at index 4"),
+        ("", "unexpected character at index 0"),
+    ];
+    for (s, msg) in cases {
+        assert_eq!(Origin::parse(s).unwrap_err().to_string(), msg, "{s}");
+    }
+}
+
+#[test]
+fn safe_redirect() {
+    let current = Uri::parse("https://example.com/app/login?next=x#top").unwrap();
+    let safe = |s: &str| UriRef::parse(s).unwrap().is_safe_redirect(&current);
+
+    assert!(safe(""));
+    assert!(safe("#section"));
+    assert!(safe("?q=1"));
+    assert!(safe("/"));
+    assert!(safe("/account/settings?tab=%2Fprofile"));
+    assert!(safe("dashboard"));
+    assert!(safe("../../../../home"));
+    assert!(safe("./a:b"));
+    assert!(safe("/.//evil.com"));
+    assert!(safe("https://example.com/home"));
+    assert!(safe("https://EXAMPLE.com:443/home"));
+
+    // Network-path references.
+    assert!(!safe("//evil.com"));
+    assert!(!safe("//example.com/home"));
+    assert!(!safe("///evil.com"));
+
+    // Authority-changing references.
+    assert!(!safe("https://evil.com/"));
+    assert!(!safe("https://example.com@evil.com/"));
+    assert!(!safe("https://u:p@example.com/"));
+    assert!(!safe("https://user@EXAMPLE.com:443/home"));
+    assert!(!safe("https://@example.com/"));
+    assert!(!safe("https://example.com.evil.com/"));
+    assert!(!safe("https://example.com:8443/"));
+    assert!(!safe("http://example.com/"));
+
+    // Dangerous schemes.
+    assert!(!safe("javascript:alert(1)"));
+    assert!(!safe("JavaScript://example.com/%0Aalert(1)"));
+    assert!(!safe("data:text/html,%3Cscript%3E"));
+    assert!(!safe("file:///etc/passwd"));
+
+    // Encoded slashes and backslashes.
+    assert!(!safe("/%2Fevil.com"));
+    assert!(!safe("/%2f%2fevil.com"));
+    assert!(!safe("/%5Cevil.com"));
+    assert!(!safe("%5c%5cevil.com"));
+
+    // The current URI must have a tuple origin.
+    let current = Uri::parse("data:text/plain,hi").unwrap();
+    assert!(!UriRef::parse("").unwrap().is_safe_redirect(&current));
+    let current = Uri::parse("file:///home/").unwrap();
+    assert!(!UriRef::parse("a").unwrap().is_safe_redirect(&current));
+}
@@ -4,6 +4,7 @@ use core::net::{Ipv4Addr, Ipv6Addr};
 use fluent_uri::{component::Host, encoding::EStr, Uri, UriRef};
 
 #[test]
+#[allow(clippy::comparison_to_empty)]
 fn parse_absolute() {
     let r = UriRef::parse("file:///etc/hosts").unwrap();
     assert_eq!(r.as_str(), "file:///etc/hosts");
@@ -51,7 +52,7 @@ fn parse_absolute() {
     #[cfg(feature = "net")]
     assert!(matches!(
         a.host_parsed(),
-        Host::Ipv6(addr) if addr == Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x7)
+        Host::Ipv6(addr, None) if addr == Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x7)
     ));
     assert_eq!(a.port(), None);
     assert_eq!(r.path(), "/c=GB");
@@ -134,7 +135,8 @@ fn parse_absolute() {
     assert_eq!(a.as_str(), "[vFe.foo.bar]");
     assert_eq!(a.userinfo(), None);
     assert_eq!(a.host(), "[vFe.foo.bar]");
-    assert!(matches!(a.host_parsed(), Host::IpvFuture { .. }));
+    assert!(matches!(a.host_parsed(), Host::IpvFuture(addr)
+        if addr.version() == "Fe" && addr.address() == "foo.bar"));
     assert_eq!(a.port(), None);
     assert_eq!(r.path(), "");
     assert_eq!(r.query(), None);
@@ -380,3 +382,181 @@ fn strict_ip_addr() {
         "invalid IPv6 address at index 3"
     );
 }
+
+#[test]
+fn parse_at_compile_time() {
+    use fluent_uri::{uri, uri_ref};
+
+    const S: &str = "foo://user@[::1]:8042/over/there?name=ferret#nose";
+
+    let r = uri!(S);
+    let parsed = Uri::parse(S).unwrap();
+    assert_eq!(r, parsed);
+    assert_eq!(r.spans(), parsed.spans());
+    #[cfg(feature = "net")]
+    assert!(matches!(
+        r.authority().unwrap().host_parsed(),
+        Host::Ipv6(Ipv6Addr::LOCALHOST, None)
+    ));
+
+    let r = uri_ref!("//127.0.0.1:80");
+    assert_eq!(r.spans(), UriRef::parse("//127.0.0.1:80").unwrap().spans());
+    #[cfg(feature = "net")]
+    assert!(matches!(
+        r.authority().unwrap().host_parsed(),
+        Host::Ipv4(Ipv4Addr::LOCALHOST)
+    ));
+
+    let r = uri_ref!("");
+    assert!(r.path().is_empty());
+
+    const BASE: Uri<&str> = Uri::parse_or_panic("http://example.com/a/b");
+    let r = UriRef::parse("../c")
+        .unwrap()
+        .resolve_against(&BASE)
+        .unwrap();
+    assert_eq!(r, "http://example.com/c");
+}
+
+#[test]
+#[should_panic = "unexpected character"]
+fn parse_or_panic() {
+    let _ = Uri::parse_or_panic("/no/scheme");
+}
+
+#[test]
+fn parse_components() {
+    use fluent_uri::component::Authority;
+
+    for s in [
+        "",
+        "example.com",
+        "user@example.com:8080",
+        "user:pass@127.0.0.1:",
+        "[::1]:443",
+        "[v1.fe]",
+        "127.0.0.1.2",
+        "%41@%42",
+    ] {
+        let a = Authority::parse(s).unwrap();
+        let r = UriRef::parse(format!("//{s}")).unwrap();
+        let b = r.authority().unwrap();
+        assert_eq!(a.as_str(), b.as_str());
+        assert_eq!(a.userinfo(), b.userinfo());
+        assert_eq!(a.host(), b.host());
+        assert_eq!(a.port(), b.port());
+        assert_eq!(
+            format!("{:?}", a.host_parsed()),
+            format!("{:?}", b.host_parsed())
+        );
+
+        let h = Host::parse(a.host()).unwrap();
+        assert_eq!(format!("{h:?}"), format!("{:?}", b.host_parsed()));
+    }
+
+    let e = Authority::parse("example.com/path").unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 11");
+    let e = Authority::parse("a:1:2").unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 3");
+    let e = Authority::parse("[::1").unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 4");
+
+    let e = Host::parse("user@host").unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 4");
+    let e = Host::parse("[::1]:80").unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 5");
+    let e = Host::parse("[1::1::1]").unwrap_err();
+    assert_eq!(e.to_string(), "invalid IPv6 address at index 1");
+
+    assert_eq!(EStr::parse_path("a/b:c@d").unwrap(), "a/b:c@d");
+    let e = EStr::parse_path("/a?b").unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 2");
+}
+
+#[cfg(not(feature = "zone-id"))]
+#[test]
+fn zone_id_disabled() {
+    assert!(Uri::parse("http://[fe80::1%25eth0]/").is_err());
+    assert!(Host::parse("[fe80::1%25eth0]").is_err());
+}
+
+#[cfg(feature = "zone-id")]
+#[test]
+fn zone_id_enabled() {
+    let uri = Uri::parse("http://[FE80::1%25eth0]/").unwrap();
+    assert_eq!(uri.authority().unwrap().host(), "[FE80::1%25eth0]");
+    assert_eq!(uri.normalize(), "http://[fe80::1%25eth0]/");
+}
+
+#[test]
+fn ipv_future() {
+    use fluent_uri::component::{IpvFuture, IpvFutureDecoder, IpvFutureRegistry, Scheme};
+
+    let Host::IpvFuture(addr) = Host::parse("[V0123456789abcdef.!$&'()*+,;=-._~:]").unwrap() else {
+        panic!("not an IPvFuture address");
+    };
+    assert_eq!(addr.version(), "0123456789abcdef");
+    assert_eq!(addr.address(), "!$&'()*+,;=-._~:");
+
+    assert!(IpvFuture::new("1", "").is_none());
+    assert!(IpvFuture::new("1.", "a").is_none());
+    assert!(IpvFuture::new("1", "a%20").is_none());
+    assert!(IpvFuture::new("1", "[a]").is_none());
+
+    // Round-trip through the builder.
+    let addr = IpvFuture::new("A", "node:42").unwrap();
+    let uri = Uri::builder()
+        .scheme(Scheme::new_or_panic("overlay"))
+        .authority_with(|b| b.host(addr).port(7))
+        .path(EStr::new_or_panic("/x"))
+        .build()
+        .unwrap();
+    assert_eq!(uri, "overlay://[vA.node:42]:7/x");
+    let Host::IpvFuture(parsed) = uri.authority().unwrap().host_parsed() else {
+        panic!("not an IPvFuture address");
+    };
+    assert_eq!(parsed.version(), "A");
+    assert_eq!(parsed.address(), "node:42");
+    assert_eq!(uri.normalize(), "overlay://[va.node:42]:7/x");
+
+    struct Node;
+
+    impl IpvFutureDecoder for Node {
+        const VERSION: &'static str = "a";
+        type Output = (String, u32);
+
+        fn decode(address: &str) -> Option<Self::Output> {
+            let (net, id) = address.split_once(':')?;
+            Some((net.into(), id.parse().ok()?))
+        }
+    }
+
+    assert_eq!(parsed.decode::<Node>(), Some(("node".into(), 42)));
+    let addr = IpvFuture::new("a", "node").unwrap();
+    assert_eq!(addr.decode::<Node>(), None);
+    let addr = IpvFuture::new("b", "node:42").unwrap();
+    assert_eq!(addr.decode::<Node>(), None);
+
+    struct Raw;
+
+    impl IpvFutureDecoder for Raw {
+        const VERSION: &'static str = "B";
+        type Output = (String, u32);
+
+        fn decode(address: &str) -> Option<Self::Output> {
+            Some((address.into(), 0))
+        }
+    }
+
+    let registry = IpvFutureRegistry::new()
+        .register::<Node>()
+        .register::<Raw>();
+    assert!(registry.contains("A"));
+    assert!(registry.contains("b"));
+    assert!(!registry.contains("c"));
+    assert_eq!(format!("{registry:?}"), r#"{"a", "B"}"#);
+    assert_eq!(registry.decode(parsed), Some(("node".into(), 42)));
+    assert_eq!(registry.decode(addr), Some(("node:42".into(), 0)));
+    assert_eq!(registry.decode(IpvFuture::new("a", "node").unwrap()), None);
+    assert_eq!(registry.decode(IpvFuture::new("c", "x").unwrap()), None);
+}
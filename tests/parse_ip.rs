@@ -15,7 +15,7 @@ fn parse_v4(s: &str) -> Option<Ipv4Addr> {
 fn parse_v6(s: &str) -> Option<Ipv6Addr> {
     let s = format!("//[{s}]");
     match UriRef::parse(s).ok()?.authority()?.host_parsed() {
-        Host::Ipv6(addr) => Some(addr),
+        Host::Ipv6(addr, _) => Some(addr),
         _ => None,
     }
 }
@@ -0,0 +1,217 @@
+#![cfg(feature = "net")]
+
+use fluent_uri::{
+    component::Host,
+    policy::{classify, DestinationPolicy, HostClass},
+    Uri,
+};
+
+fn class(host: &str) -> HostClass {
+    classify(Host::parse(host).unwrap())
+}
+
+#[test]
+fn classify_ipv4() {
+    let cases = [
+        ("0.0.0.0", HostClass::Unspecified),
+        ("0.1.2.3", HostClass::Unspecified),
+        ("127.0.0.1", HostClass::Loopback),
+        ("127.255.255.254", HostClass::Loopback),
+        ("10.0.0.1", HostClass::Private),
+        ("172.16.0.1", HostClass::Private),
+        ("172.31.255.255", HostClass::Private),
+        ("172.32.0.1", HostClass::Public),
+        ("192.168.1.1", HostClass::Private),
+        ("100.64.0.1", HostClass::SharedAddress),
+        ("100.127.255.255", HostClass::SharedAddress),
+        ("100.128.0.1", HostClass::Public),
+        ("169.254.169.254", HostClass::LinkLocal),
+        ("192.0.2.1", HostClass::Documentation),
+        ("198.51.100.1", HostClass::Documentation),
+        ("203.0.113.1", HostClass::Documentation),
+        ("224.0.0.1", HostClass::Multicast),
+        ("239.255.255.250", HostClass::Multicast),
+        ("255.255.255.255", HostClass::Broadcast),
+        ("240.0.0.1", HostClass::Reserved),
+        ("198.18.0.1", HostClass::Reserved),
+        ("192.0.0.8", HostClass::Reserved),
+        ("8.8.8.8", HostClass::Public),
+        ("1.1.1.1", HostClass::Public),
+    ];
+    for (host, expected) in cases {
+        assert_eq!(class(host), expected, "{host}");
+    }
+}
+
+#[test]
+fn classify_ipv6() {
+    let cases = [
+        ("[::]", HostClass::Unspecified),
+        ("[::1]", HostClass::Loopback),
+        ("[::ffff:127.0.0.1]", HostClass::Ipv4Mapped),
+        ("[::ffff:8.8.8.8]", HostClass::Ipv4Mapped),
+        ("[64:ff9b::10.0.0.1]", HostClass::Private),
+        ("[64:ff9b::8.8.8.8]", HostClass::Public),
+        ("[fc00::1]", HostClass::UniqueLocal),
+        ("[fdff::1]", HostClass::UniqueLocal),
+        ("[fe80::1]", HostClass::LinkLocal),
+        ("[febf::1]", HostClass::LinkLocal),
+        ("[fec0::1]", HostClass::Reserved),
+        ("[ff02::1]", HostClass::Multicast),
+        ("[2001:db8::1]", HostClass::Documentation),
+        ("[::127.0.0.1]", HostClass::Reserved),
+        ("[100::1]", HostClass::Reserved),
+        ("[2002:7f00:1::]", HostClass::Loopback),
+        ("[2002:a00:1::1]", HostClass::Private),
+        ("[2002:808:808::]", HostClass::Public),
+        (
+            "[2001:0:4136:e378:8000:63bf:80ff:fffe]",
+            HostClass::Loopback,
+        ),
+        ("[2001:0:4136:e378:8000:63bf:f7f7:f7f7]", HostClass::Public),
+        ("[2606:4700:4700::1111]", HostClass::Public),
+        ("[v1.x]", HostClass::Reserved),
+    ];
+    for (host, expected) in cases {
+        assert_eq!(class(host), expected, "{host}");
+    }
+}
+
+#[test]
+fn classify_reg_name() {
+    let cases = [
+        ("localhost", HostClass::Loopback),
+        ("LocalHost.", HostClass::Loopback),
+        ("api.localhost", HostClass::Loopback),
+        ("%6Cocalhost", HostClass::Loopback),
+        ("metadata.google.internal", HostClass::SpecialUseName),
+        ("printer.local", HostClass::SpecialUseName),
+        ("router.home.arpa", HostClass::SpecialUseName),
+        ("foo.test", HostClass::SpecialUseName),
+        ("example", HostClass::SpecialUseName),
+        ("x.onion", HostClass::SpecialUseName),
+        ("127.1", HostClass::Loopback),
+        ("2130706433", HostClass::Loopback),
+        ("0x7f.0.0.1", HostClass::Loopback),
+        ("0X7F000001", HostClass::Loopback),
+        ("0177.0.0.1", HostClass::Loopback),
+        ("127.0.0.1.", HostClass::Loopback),
+        ("10.0x10000", HostClass::Private),
+        ("169.254.43518", HostClass::LinkLocal),
+        ("0", HostClass::Unspecified),
+        ("0x", HostClass::Unspecified),
+        ("8.8.2056", HostClass::Public),
+        ("1.2.3.4.5", HostClass::Reserved),
+        ("256.0.0.1", HostClass::Reserved),
+        ("1.2.3.256", HostClass::Reserved),
+        ("4294967296", HostClass::Reserved),
+        ("09.1", HostClass::Reserved),
+        ("foo.1", HostClass::Reserved),
+        ("1.example.com", HostClass::Public),
+        ("0xg.com", HostClass::Public),
+        ("notlocalhost", HostClass::Public),
+        ("internal.example.com", HostClass::Public),
+        ("mylocal", HostClass::Public),
+        ("example.com", HostClass::Public),
+        ("", HostClass::Public),
+    ];
+    for (host, expected) in cases {
+        assert_eq!(class(host), expected, "{host}");
+    }
+}
+
+#[test]
+fn destination_policy() {
+    let check = |policy: &DestinationPolicy, s: &str| policy.check(&Uri::parse(s).unwrap());
+
+    let policy = DestinationPolicy::new();
+    assert!(check(&policy, "https://example.com/").is_ok());
+    assert!(check(&policy, "https://8.8.8.8/").is_ok());
+
+    let e = check(&policy, "http://169.254.169.254/latest/meta-data").unwrap_err();
+    assert_eq!(e.host_class(), Some(HostClass::LinkLocal));
+    assert_eq!(
+        e.to_string(),
+        "destination host is link-local and not allowed"
+    );
+
+    let e = check(&policy, "http://[::ffff:7f00:1]/").unwrap_err();
+    assert_eq!(e.host_class(), Some(HostClass::Ipv4Mapped));
+
+    let e = check(&policy, "file:///etc/passwd").unwrap_err();
+    assert_eq!(e.host_class(), None);
+    assert_eq!(e.to_string(), "destination has no host");
+    assert!(check(&policy, "mailto:a@example.com").is_err());
+
+    let policy = DestinationPolicy::new()
+        .allow_class(HostClass::Private)
+        .deny_class(HostClass::Public)
+        .allow_host("Example.COM.")
+        .unwrap()
+        .allow_host("[0::1]")
+        .unwrap()
+        .deny_host("10.0.0.1")
+        .unwrap();
+    assert!(check(&policy, "http://10.1.1.1/").is_ok());
+    assert!(check(&policy, "http://example.com/").is_ok());
+    assert!(check(&policy, "http://%65xample.com/").is_ok());
+    assert!(check(&policy, "http://[::1]:8080/").is_ok());
+    assert!(check(&policy, "http://other.com/").is_err());
+
+    let e = check(&policy, "http://10.0.0.1/").unwrap_err();
+    assert_eq!(e.host_class(), None);
+    assert_eq!(e.to_string(), "destination host is denied");
+
+    // The deny list takes precedence over the allow list.
+    let policy = DestinationPolicy::default()
+        .allow_host("a.example")
+        .unwrap()
+        .deny_host("A.example")
+        .unwrap();
+    assert!(check(&policy, "http://a.example/").is_err());
+
+    assert!(DestinationPolicy::new().allow_host("a b").is_err());
+    assert!(DestinationPolicy::new().deny_host("[::1").is_err());
+    let policy = DestinationPolicy::new().deny_host("[V1.X]").unwrap();
+    let e = check(&policy, "http://[v1.x]/").unwrap_err();
+    assert_eq!(e.to_string(), "destination host is denied");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn check_socket_addrs() {
+    use std::{
+        io,
+        net::{Ipv4Addr, SocketAddr},
+    };
+
+    let policy = DestinationPolicy::new();
+
+    let uri = Uri::parse("http://8.8.8.8:8080/").unwrap();
+    let addrs = policy.check_socket_addrs(&uri, 80).unwrap();
+    assert_eq!(addrs, [SocketAddr::from((Ipv4Addr::new(8, 8, 8, 8), 8080))]);
+
+    let uri = Uri::parse("http://127.0.0.1/").unwrap();
+    let e = policy.check_socket_addrs(&uri, 80).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::PermissionDenied);
+    let inner = e
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<fluent_uri::error::PolicyError>()
+        .unwrap();
+    assert_eq!(inner.host_class(), Some(HostClass::Loopback));
+
+    let uri = Uri::parse("http://localhost/").unwrap();
+    let e = policy.check_socket_addrs(&uri, 80).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::PermissionDenied);
+
+    // Addresses of a host on the allow list are not checked.
+    let policy = DestinationPolicy::new().allow_host("127.0.0.1").unwrap();
+    let uri = Uri::parse("http://127.0.0.1:1/").unwrap();
+    let addrs = policy.check_socket_addrs(&uri, 80).unwrap();
+    assert_eq!(addrs, [SocketAddr::from((Ipv4Addr::LOCALHOST, 1))]);
+
+    let uri = Uri::parse("http://8.8.8.8:99999/").unwrap();
+    let e = policy.check_socket_addrs(&uri, 80).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+}
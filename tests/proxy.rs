@@ -0,0 +1,143 @@
+#![cfg(feature = "net")]
+
+use fluent_uri::{
+    proxy::{ProxyBypass, ProxyConfig},
+    Uri,
+};
+
+fn matches(bypass: &ProxyBypass, s: &str) -> bool {
+    bypass.matches(&Uri::parse(s).unwrap())
+}
+
+#[test]
+fn bypass_domains() {
+    let bypass = ProxyBypass::from_env_value("localhost, .corp  *.Example.COM\texample.org.,,");
+
+    assert!(matches(&bypass, "http://localhost/"));
+    assert!(matches(&bypass, "http://LOCALHOST.:8080/"));
+    assert!(matches(&bypass, "http://api.localhost/"));
+    assert!(matches(&bypass, "https://corp/"));
+    assert!(matches(&bypass, "https://git.corp/"));
+    assert!(matches(&bypass, "https://a.b.corp./"));
+    assert!(matches(&bypass, "https://example.com/"));
+    assert!(matches(&bypass, "https://www.example.com/"));
+    assert!(matches(&bypass, "https://www.example.org/"));
+    assert!(matches(&bypass, "https://%65xample.org/"));
+
+    assert!(!matches(&bypass, "http://notlocalhost/"));
+    assert!(!matches(&bypass, "http://example.com.evil.net/"));
+    assert!(!matches(&bypass, "http://corp.example.net/"));
+    assert!(!matches(&bypass, "http://127.0.0.1/"));
+    assert!(!matches(&bypass, "file:///etc/hosts"));
+    assert!(!matches(&bypass, "mailto:a@example.com"));
+
+    // A resolver would see a name other than the one matched.
+    assert!(!matches(&bypass, "http://evil.net%00.corp/"));
+    assert!(!matches(&bypass, "http://evil.net%2F.corp/"));
+    assert!(!matches(&bypass, "http://evil.net%FF.example.com/"));
+}
+
+#[test]
+fn bypass_ips() {
+    let bypass = ProxyBypass::from_env_value(
+        "127.0.0.1,::1,[fe80::1],10.0.0.0/8,192.168.1.0/24,fc00::/7,[2001:db8::]/32,0.0.0.0/0",
+    );
+    assert!(matches(&bypass, "http://127.0.0.1/"));
+    assert!(matches(&bypass, "http://[::1]/"));
+    assert!(matches(&bypass, "http://[0:0::1]:8080/"));
+    assert!(matches(&bypass, "http://[fe80::1]/"));
+    assert!(matches(&bypass, "http://10.255.0.1/"));
+    assert!(matches(&bypass, "http://[::ffff:10.0.0.1]/"));
+    assert!(matches(&bypass, "http://[fd12::1]/"));
+    assert!(matches(&bypass, "http://[2001:db8:1::1]/"));
+    // 0.0.0.0/0 matches all IPv4 addresses.
+    assert!(matches(&bypass, "http://8.8.8.8/"));
+    assert!(!matches(&bypass, "http://[2001:db9::1]/"));
+    assert!(!matches(&bypass, "http://[::2]/"));
+    assert!(!matches(&bypass, "http://localhost/"));
+
+    let bypass = ProxyBypass::from_env_value("192.168.1.0/24,[::ffff:127.0.0.1]");
+    assert!(matches(&bypass, "http://192.168.1.255/"));
+    assert!(!matches(&bypass, "http://192.168.2.1/"));
+    assert!(matches(&bypass, "http://127.0.0.1/"));
+}
+
+#[test]
+fn bypass_ports() {
+    let bypass = ProxyBypass::from_env_value("example.com:8080,127.0.0.1:443,[::1]:80");
+
+    assert!(matches(&bypass, "http://example.com:8080/"));
+    assert!(matches(&bypass, "http://www.example.com:8080/"));
+    assert!(!matches(&bypass, "http://example.com/"));
+    assert!(!matches(&bypass, "http://example.com:80/"));
+
+    assert!(matches(&bypass, "https://127.0.0.1/"));
+    assert!(matches(&bypass, "http://127.0.0.1:443/"));
+    assert!(!matches(&bypass, "http://127.0.0.1/"));
+    assert!(!matches(&bypass, "foo://127.0.0.1/"));
+
+    assert!(matches(&bypass, "http://[::1]/"));
+    assert!(matches(&bypass, "ws://[::1]/"));
+    assert!(!matches(&bypass, "https://[::1]/"));
+}
+
+#[test]
+fn bypass_any_and_invalid() {
+    let bypass = ProxyBypass::from_env_value("*");
+    assert!(matches(&bypass, "http://example.com/"));
+    assert!(matches(&bypass, "http://[::1]:1/"));
+    assert!(!matches(&bypass, "urn:isbn:0451450523"));
+
+    let bypass = ProxyBypass::from_env_value(
+        "10.0.0.0/33,::/129,a/8,example.com:http,[::1,[::1]x,[foo]:80,.,*.,f*o.com,:80",
+    );
+    assert!(!matches(&bypass, "http://10.0.0.1/"));
+    assert!(!matches(&bypass, "http://[::1]/"));
+    assert!(!matches(&bypass, "http://example.com/"));
+    assert!(!matches(&bypass, "http://foo/"));
+    assert!(!matches(&bypass, "http://fxo.com/"));
+
+    assert!(!matches(&ProxyBypass::default(), "http://localhost/"));
+    assert!(!matches(
+        &ProxyBypass::from_env_value(""),
+        "http://localhost/"
+    ));
+}
+
+#[test]
+fn config() {
+    let config = ProxyConfig::from_env_values(
+        Some(" proxy.corp:3128 "),
+        Some("socks5h://127.0.0.1:1080"),
+        Some("localhost"),
+    )
+    .unwrap();
+    assert_eq!(config.http_proxy().unwrap(), "http://proxy.corp:3128");
+    assert_eq!(config.https_proxy().unwrap(), "socks5h://127.0.0.1:1080");
+    assert!(config
+        .bypass()
+        .matches(&Uri::parse("http://localhost/").unwrap()));
+
+    let proxy_for = |s| config.proxy_for(&Uri::parse(s).unwrap());
+    assert_eq!(proxy_for("http://example.com/"), config.http_proxy());
+    assert_eq!(proxy_for("HTTP://example.com/"), config.http_proxy());
+    assert_eq!(proxy_for("https://example.com/"), config.https_proxy());
+    assert_eq!(proxy_for("https://localhost:8443/"), None);
+    assert_eq!(proxy_for("ws://example.com/"), None);
+
+    let config = ProxyConfig::from_env_values(None, Some(""), None).unwrap();
+    assert!(config.http_proxy().is_none());
+    assert!(config.https_proxy().is_none());
+    assert_eq!(config.proxy_for(&Uri::parse("http://a/").unwrap()), None);
+
+    let e = ProxyConfig::from_env_values(Some("proxy corp:80"), None, None).unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 5");
+    let e = ProxyConfig::from_env_values(None, Some("http://[::1/"), None).unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 11");
+
+    // Leading whitespace counts toward the error index.
+    let e = ProxyConfig::from_env_values(Some("  proxy:abc"), None, None).unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 8");
+    let e = ProxyConfig::from_env_values(None, Some("\thttp://[::1/ "), None).unwrap_err();
+    assert_eq!(e.to_string(), "unexpected character at index 12");
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "psl")]
+
+use fluent_uri::{
+    component::Host,
+    psl::{is_same_site, List},
+    Uri,
+};
+
+fn suffix(host: &str) -> Option<&str> {
+    Host::parse(host)
+        .unwrap()
+        .public_suffix()
+        .map(|s| s.as_str())
+}
+
+fn domain(host: &str) -> Option<&str> {
+    Host::parse(host)
+        .unwrap()
+        .registrable_domain()
+        .map(|s| s.as_str())
+}
+
+#[test]
+fn embedded_list() {
+    // Test cases adapted from the Public Suffix List test data.
+    assert_eq!(suffix("com"), Some("com"));
+    assert_eq!(domain("com"), None);
+    assert_eq!(domain("example.com"), Some("example.com"));
+    assert_eq!(domain("b.example.com"), Some("example.com"));
+    assert_eq!(domain("a.b.example.com"), Some("example.com"));
+
+    assert_eq!(domain("example"), None);
+    assert_eq!(domain("b.example"), Some("b.example"));
+
+    assert_eq!(suffix("a.b.co.uk"), Some("co.uk"));
+    assert_eq!(domain("a.b.co.uk"), Some("b.co.uk"));
+    assert_eq!(domain("co.uk"), None);
+
+    // Wildcard and exception rules.
+    assert_eq!(domain("ck"), None);
+    assert_eq!(domain("test.ck"), None);
+    assert_eq!(domain("b.test.ck"), Some("b.test.ck"));
+    assert_eq!(domain("a.b.test.ck"), Some("b.test.ck"));
+    assert_eq!(suffix("www.ck"), Some("ck"));
+    assert_eq!(domain("www.ck"), Some("www.ck"));
+    assert_eq!(domain("www.www.ck"), Some("www.ck"));
+
+    // Private section.
+    assert_eq!(domain("foo.blogspot.co.uk"), Some("foo.blogspot.co.uk"));
+
+    // Case-insensitivity and percent-encoding.
+    assert_eq!(domain("WWW.Example.COM"), Some("Example.COM"));
+    assert_eq!(domain("a.%63o.uk"), Some("a.%63o.uk"));
+
+    // Internationalized domain names.
+    assert_eq!(
+        domain("www.%E9%A3%9F%E7%8B%AE.%E5%85%AC%E5%8F%B8.cn"),
+        Some("%E9%A3%9F%E7%8B%AE.%E5%85%AC%E5%8F%B8.cn")
+    );
+    assert_eq!(
+        domain("www.xn--85x722f.xn--55qx5d.cn"),
+        Some("xn--85x722f.xn--55qx5d.cn")
+    );
+    assert_eq!(suffix("xn--85x722f.XN--55QX5D.cn"), Some("XN--55QX5D.cn"));
+
+    // Fully qualified names.
+    assert_eq!(suffix("www.example.co.uk."), Some("co.uk"));
+    assert_eq!(domain("www.example.co.uk."), Some("example.co.uk"));
+    assert_eq!(domain("example.com."), Some("example.com"));
+    assert_eq!(domain("com."), None);
+
+    // Invalid hosts.
+    assert_eq!(domain("example.com.."), None);
+    assert_eq!(domain("."), None);
+    assert_eq!(domain(".example.com"), None);
+    assert_eq!(domain("%FF.com"), None);
+    assert_eq!(suffix("a.%FF"), None);
+    assert_eq!(domain("xn--99999999999.example.com"), None);
+    assert_eq!(suffix("a.xn--99999999999"), None);
+    assert_eq!(domain("127.0.0.1"), None);
+    assert_eq!(domain("[::1]"), None);
+}
+
+#[test]
+fn custom_list() {
+    let list = List::parse(
+        "// comment\n\
+         \n\
+         uk\n\
+         CO.uk extra text\n\
+         *.platform.example\n\
+         !admin.platform.example\n",
+    );
+    let host = Host::parse("a.b.co.uk").unwrap();
+    assert_eq!(list.public_suffix(host).unwrap(), "co.uk");
+
+    let host = Host::parse("x.y.platform.example").unwrap();
+    assert_eq!(list.public_suffix(host).unwrap(), "y.platform.example");
+    assert_eq!(
+        list.registrable_domain(host).unwrap(),
+        "x.y.platform.example"
+    );
+
+    let host = Host::parse("admin.platform.example").unwrap();
+    assert_eq!(list.public_suffix(host).unwrap(), "platform.example");
+
+    // The default rule applies when no rule matches.
+    let host = Host::parse("a.b.com").unwrap();
+    assert_eq!(list.public_suffix(host).unwrap(), "com");
+
+    let list = List::default();
+    assert_eq!(list.registrable_domain(host).unwrap(), "b.com");
+}
+
+#[test]
+fn from_file() {
+    let path = std::env::temp_dir().join("fluent-uri-psl-test.dat");
+    std::fs::write(&path, "example\n").unwrap();
+    let list = List::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let host = Host::parse("a.b.example").unwrap();
+    assert_eq!(list.registrable_domain(host).unwrap(), "b.example");
+
+    assert!(List::from_file(std::env::temp_dir().join("fluent-uri-nonexistent")).is_err());
+}
+
+#[test]
+fn same_site() {
+    let same = |a: &str, b: &str| is_same_site(&Uri::parse(a).unwrap(), &Uri::parse(b).unwrap());
+
+    assert!(same(
+        "https://example.com/",
+        "https://www.example.com:8443/a"
+    ));
+    assert!(same("https://a.b.co.uk/", "HTTPS://c.B.CO.UK/"));
+    assert!(same(
+        "https://xn--85x722f.com/",
+        "https://www.%E9%A3%9F%E7%8B%AE.com/"
+    ));
+    assert!(same("https://com/", "https://com/"));
+    assert!(same(
+        "https://www.example.co.uk./",
+        "https://example.co.uk/"
+    ));
+    assert!(same("http://127.0.0.1/", "http://127.0.0.1:8080/"));
+    assert!(same("http://[::1]/", "http://[0:0::1]/"));
+
+    assert!(!same("https://example.com/", "http://example.com/"));
+    assert!(!same("https://a.co.uk/", "https://b.co.uk/"));
+    assert!(!same(
+        "https://a.foo.blogspot.co.uk/",
+        "https://bar.blogspot.co.uk/"
+    ));
+    assert!(!same("http://127.0.0.1/", "http://127.0.0.2/"));
+    assert!(!same("file:///a", "file:///a"));
+    assert!(!same("http://%FF/", "http://%FF/"));
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "security")]
+
+use fluent_uri::{component::Host, security::Issue, Uri};
+
+fn check(host: &str) -> fluent_uri::security::SecurityReport {
+    Host::parse(host).unwrap().security_report()
+}
+
+#[test]
+fn clean_hosts() {
+    for host in [
+        "example.com",
+        "EXAMPLE.com",
+        "b%C3%BCcher.example",
+        "%E4%BE%8B%E3%81%88.jp",
+        "xn--r8jz45g.jp",
+        "127.0.0.1",
+        "[::1]",
+        "[v1.x]",
+    ] {
+        let report = check(host);
+        assert!(report.is_clean(), "{host}: {:?}", report.issues());
+    }
+
+    assert_eq!(check("127.0.0.1").unicode(), None);
+    assert_eq!(check("EXAMPLE.com").unicode(), Some("example.com"));
+    assert_eq!(check("xn--r8jz45g.jp").unicode(), Some("例え.jp"));
+    assert_eq!(check("example.com").ascii_lookalike(), None);
+}
+
+#[test]
+fn confusables() {
+    // Cyrillic "ѕсоре".
+    let report = check("%D1%95%D1%81%D0%BE%D1%80%D0%B5.example");
+    assert_eq!(report.unicode(), Some("ѕсоре.example"));
+    assert_eq!(
+        report.issues(),
+        [
+            Issue::WholeScriptConfusable { label: 0 },
+            Issue::AsciiLookalike {
+                label: 0,
+                lookalike: "scope".into()
+            }
+        ]
+    );
+    assert_eq!(report.ascii_lookalike().as_deref(), Some("scope.example"));
+
+    // Latin "g" followed by Cyrillic "оо" and Latin "gle".
+    let report = check("www.g%D0%BE%D0%BEgle.com");
+    assert_eq!(
+        report.issues(),
+        [
+            Issue::MixedScript { label: 1 },
+            Issue::AsciiLookalike {
+                label: 1,
+                lookalike: "google".into()
+            }
+        ]
+    );
+    assert_eq!(report.ascii_lookalike().as_deref(), Some("www.google.com"));
+
+    // Greek "λ" alone is single-script and not confusable with ASCII.
+    let report = check("%CE%BB.example");
+    assert!(report.is_clean());
+
+    // Latin mixed with Greek without ASCII lookalike.
+    let report = check("a%CE%BB.example");
+    assert_eq!(report.issues(), [Issue::MixedScript { label: 0 }]);
+    assert_eq!(report.ascii_lookalike(), None);
+}
+
+#[test]
+fn invisible_and_bidi() {
+    let report = check("exa%E2%80%8Bmple.com");
+    assert!(report.issues().contains(&Issue::InvisibleChar('\u{200B}')));
+
+    let report = check("exa%C2%ADmple.com");
+    assert!(report.issues().contains(&Issue::InvisibleChar('\u{AD}')));
+
+    let report = check("%E2%80%AEmoc.example");
+    assert!(report.issues().contains(&Issue::BidiControl('\u{202E}')));
+}
+
+#[test]
+fn malformed() {
+    let report = check("%FF.com");
+    assert_eq!(report.issues(), [Issue::Malformed]);
+    assert_eq!(report.unicode(), None);
+
+    // A registered name not decoding to UTF-8 has no Unicode form.
+    let report = check("a%C3.com");
+    assert_eq!(report.issues(), [Issue::Malformed]);
+    assert_eq!(report.unicode(), None);
+
+    // A name failing UTS #46 processing still has a Unicode form.
+    let report = check("xn--a.com");
+    assert!(report.issues().contains(&Issue::Malformed));
+    assert!(report.unicode().is_some());
+}
+
+#[test]
+fn from_uri() {
+    let uri = Uri::parse("https://xn--e1argc3h.example/login").unwrap();
+    let report = uri.authority().unwrap().host_parsed().security_report();
+    assert_eq!(report.ascii_lookalike().as_deref(), Some("scope.example"));
+}
@@ -0,0 +1,41 @@
+use fluent_uri::{Uri, UriRef};
+
+#[test]
+fn spans() {
+    let s = "foo://user@example.com:8042/over/there?name=ferret#nose";
+    let spans = Uri::parse(s).unwrap().spans();
+    assert_eq!(spans.scheme, Some(0..3));
+    assert_eq!(spans.authority, Some(6..27));
+    assert_eq!(spans.userinfo, Some(6..10));
+    assert_eq!(spans.host, Some(11..22));
+    assert_eq!(spans.port, Some(23..27));
+    assert_eq!(spans.path, 27..38);
+    assert_eq!(spans.query, Some(39..50));
+    assert_eq!(spans.fragment, Some(51..55));
+
+    let spans = UriRef::parse("//[::1]:").unwrap().spans();
+    assert_eq!(spans.scheme, None);
+    assert_eq!(spans.authority, Some(2..8));
+    assert_eq!(spans.userinfo, None);
+    assert_eq!(spans.host, Some(2..7));
+    assert_eq!(spans.port, Some(8..8));
+    assert_eq!(spans.path, 8..8);
+
+    let spans = UriRef::parse("file:///etc/hosts").unwrap().spans();
+    assert_eq!(spans.authority, Some(7..7));
+    assert_eq!(spans.host, Some(7..7));
+    assert_eq!(spans.port, None);
+    assert_eq!(spans.path, 7..17);
+
+    let spans = UriRef::parse("@a?#").unwrap().spans();
+    assert_eq!(spans.authority, None);
+    assert_eq!(spans.host, None);
+    assert_eq!(spans.path, 0..2);
+    assert_eq!(spans.query, Some(3..3));
+    assert_eq!(spans.fragment, Some(4..4));
+
+    let spans = UriRef::parse("").unwrap().spans();
+    assert_eq!(spans.path, 0..0);
+    assert_eq!(spans.query, None);
+    assert_eq!(spans.fragment, None);
+}
@@ -0,0 +1,122 @@
+use fluent_uri::{spiffe::SpiffeId, Uri};
+
+fn error(s: &str) -> String {
+    SpiffeId::parse(s).unwrap_err().to_string()
+}
+
+#[test]
+fn parse() {
+    let id = SpiffeId::parse("spiffe://example.org/ns/prod/sa/web").unwrap();
+    assert_eq!(id, "spiffe://example.org/ns/prod/sa/web");
+    assert_eq!(id.trust_domain(), "example.org");
+    assert_eq!(id.path(), "/ns/prod/sa/web");
+    assert!(id.path_segments().eq(["ns", "prod", "sa", "web"]));
+    assert!(id.path_segments().rev().eq(["web", "sa", "prod", "ns"]));
+    assert_eq!(format!("{id}"), "spiffe://example.org/ns/prod/sa/web");
+    assert_eq!(format!("{id:?}"), "\"spiffe://example.org/ns/prod/sa/web\"");
+
+    let id = SpiffeId::parse("spiffe://my_td-1.example").unwrap();
+    assert_eq!(id.trust_domain(), "my_td-1.example");
+    assert_eq!(id.path(), "");
+    assert_eq!(id.path_segments().count(), 0);
+
+    let id = SpiffeId::parse("spiffe://td/A.b_c-D/.../..x").unwrap();
+    assert!(id.path_segments().eq(["A.b_c-D", "...", "..x"]));
+
+    // An IPv4 address is syntactically a valid trust domain.
+    assert!(SpiffeId::parse("spiffe://127.0.0.1/web").is_ok());
+
+    let uri = Uri::parse(String::from("spiffe://td/web")).unwrap();
+    let id = SpiffeId::try_from(uri).unwrap();
+    assert_eq!(id.as_uri(), "spiffe://td/web");
+    assert_eq!(id.into_uri(), "spiffe://td/web");
+}
+
+#[test]
+fn invalid() {
+    let long_td = "a".repeat(256);
+    let long_path = "a".repeat(2048);
+    let cases = [
+        ("spiffe:/td/web", "empty trust domain"),
+        ("spiffe:///web", "empty trust domain"),
+        ("spiffe://", "empty trust domain"),
+        ("SPIFFE://td/web", "scheme other than \"spiffe\""),
+        ("https://td/web", "scheme other than \"spiffe\""),
+        ("spiffe://TD/web", "invalid trust domain"),
+        ("spiffe://t%64/web", "invalid trust domain"),
+        ("spiffe://t~d/web", "invalid trust domain"),
+        ("spiffe://[::1]/web", "invalid trust domain"),
+        ("spiffe://u@td/web", "SPIFFE ID with userinfo"),
+        ("spiffe://td:8080/web", "SPIFFE ID with port"),
+        ("spiffe://td:/web", "SPIFFE ID with port"),
+        ("spiffe://td/web?x", "SPIFFE ID with query"),
+        ("spiffe://td/web#x", "SPIFFE ID with fragment"),
+        ("spiffe://td/", "empty path segment in SPIFFE ID"),
+        ("spiffe://td/web/", "empty path segment in SPIFFE ID"),
+        ("spiffe://td//web", "empty path segment in SPIFFE ID"),
+        ("spiffe://td/./web", "dot segment in SPIFFE ID"),
+        ("spiffe://td/a/..", "dot segment in SPIFFE ID"),
+        ("spiffe://td/a%20b", "invalid character in SPIFFE ID path"),
+        ("spiffe://td/a:b", "invalid character in SPIFFE ID path"),
+        ("spiffe://td/a~b", "invalid character in SPIFFE ID path"),
+        ("spiffe td", "invalid URI"),
+        ("//td/web", "invalid URI"),
+    ];
+    for (s, msg) in cases {
+        assert_eq!(error(s), msg, "{s}");
+    }
+
+    assert_eq!(
+        error(&format!("spiffe://{long_td}/web")),
+        "invalid trust domain"
+    );
+    assert!(SpiffeId::parse(&format!("spiffe://{}/web", &long_td[1..])).is_ok());
+    assert_eq!(
+        error(&format!("spiffe://td/{long_path}")),
+        "SPIFFE ID longer than 2048 octets"
+    );
+}
+
+#[test]
+fn builder() {
+    let id = SpiffeId::builder("example.org")
+        .segment("ns")
+        .segment("prod")
+        .build()
+        .unwrap();
+    assert_eq!(id, "spiffe://example.org/ns/prod");
+    assert_eq!(id, SpiffeId::parse("spiffe://example.org/ns/prod").unwrap());
+
+    let id = SpiffeId::builder("example.org").build().unwrap();
+    assert_eq!(id, "spiffe://example.org");
+
+    let err = |b: fluent_uri::spiffe::SpiffeIdBuilder| b.build().unwrap_err().to_string();
+    assert_eq!(err(SpiffeId::builder("")), "empty trust domain");
+    assert_eq!(
+        err(SpiffeId::builder("Example.org")),
+        "invalid trust domain"
+    );
+    assert_eq!(err(SpiffeId::builder("td:80")), "invalid trust domain");
+    assert_eq!(err(SpiffeId::builder("u@td")), "invalid trust domain");
+    assert_eq!(
+        err(SpiffeId::builder("td").segment("")),
+        "empty path segment in SPIFFE ID"
+    );
+    assert_eq!(
+        err(SpiffeId::builder("td").segment("..")),
+        "dot segment in SPIFFE ID"
+    );
+    assert_eq!(
+        err(SpiffeId::builder("td").segment("a/b")),
+        "invalid character in SPIFFE ID path"
+    );
+    assert_eq!(
+        err(SpiffeId::builder("td").segment("a?b")),
+        "invalid character in SPIFFE ID path"
+    );
+    let long = "a".repeat(1024);
+    assert_eq!(
+        err(SpiffeId::builder("td").segment(&long).segment(&long)),
+        "SPIFFE ID longer than 2048 octets"
+    );
+}
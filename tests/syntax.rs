@@ -0,0 +1,152 @@
+use borrow_or_share::Bos;
+use fluent_uri::{
+    component::Scheme,
+    encoding::EStr,
+    error::SchemeSyntaxError,
+    syntax::{File, Http, SchemeRegistry, SchemeSyntax},
+    Uri,
+};
+
+struct Mailto;
+
+impl SchemeSyntax for Mailto {
+    const SCHEMES: &'static [&'static Scheme] = &[Scheme::new_or_panic("mailto")];
+    type Output<T: Bos<str>> = Uri<T>;
+
+    fn validate<T: Bos<str>>(uri: Uri<T>) -> Result<Uri<T>, SchemeSyntaxError> {
+        if uri.has_authority() || !uri.path().as_str().contains('@') {
+            return Err(SchemeSyntaxError::invalid());
+        }
+        Ok(uri)
+    }
+}
+
+struct StrictHttp;
+
+impl SchemeSyntax for StrictHttp {
+    const SCHEMES: &'static [&'static Scheme] = &[Scheme::new_or_panic("http")];
+    type Output<T: Bos<str>> = Uri<T>;
+
+    fn validate<T: Bos<str>>(_uri: Uri<T>) -> Result<Uri<T>, SchemeSyntaxError> {
+        Err(SchemeSyntaxError::invalid())
+    }
+}
+
+#[test]
+fn parse_with() {
+    let uri = Uri::parse_with::<Http>("HTTPS://example.com:8443/a?b").unwrap();
+    assert!(uri.is_tls());
+    assert_eq!(uri.port_u16(), 8443);
+    assert_eq!(uri.path_and_query(), "/a?b");
+
+    let err = |s| Uri::parse_with::<Http>(s).unwrap_err().to_string();
+    assert_eq!(err("ftp://example.com/"), "scheme not supported by syntax");
+    assert_eq!(err("http:///a"), "missing or empty host in HTTP(S) URI");
+    assert_eq!(err("http://u@example.com/"), "HTTP(S) URI with userinfo");
+    assert_eq!(err("http://a b/"), "unexpected character at index 8");
+
+    let uri = Uri::parse_with::<File>("file:///etc/hosts").unwrap();
+    assert_eq!(uri.path(), "/etc/hosts");
+    assert!(Uri::parse_with::<File>("file://server/share/a.txt").is_ok());
+    assert!(Uri::parse_with::<File>("file:/etc/hosts").is_ok());
+
+    let err = |s| Uri::parse_with::<File>(s).unwrap_err().to_string();
+    assert_eq!(err("file://u@host/a"), "file URI with userinfo");
+    assert_eq!(err("file://host:21/a"), "file URI with port");
+    assert_eq!(err("file://host"), "file URI with relative or empty path");
+    assert_eq!(err("file:a/b"), "file URI with relative or empty path");
+    assert_eq!(err("file:///a?b"), "file URI with query");
+
+    assert!(Uri::parse_with::<Mailto>("mailto:user@example.com").is_ok());
+    assert!(Uri::parse_with::<Mailto>("mailto:user").is_err());
+    assert!(Uri::parse_with::<Mailto>("mailto://user@example.com").is_err());
+}
+
+#[test]
+fn validate_with() {
+    let uri = Uri::parse(String::from("http://example.com/")).unwrap();
+    let http = uri.clone().validate_with::<Http>().unwrap();
+    assert_eq!(http.into_uri(), uri);
+    assert!(uri.validate_with::<File>().is_err());
+}
+
+#[test]
+fn registry() {
+    let registry = SchemeRegistry::new();
+    assert!(!registry.contains(Scheme::new_or_panic("http")));
+    assert!(registry
+        .validate(&Uri::parse("http://u@example.com/").unwrap())
+        .is_ok());
+
+    let registry = SchemeRegistry::builtin().register::<Mailto>();
+    assert!(registry.contains(Scheme::new_or_panic("HTTPS")));
+    assert!(registry.contains(Scheme::new_or_panic("file")));
+    assert!(registry.contains(Scheme::new_or_panic("mailto")));
+    assert!(!registry.contains(Scheme::new_or_panic("ftp")));
+
+    let validate = |s| registry.validate(&Uri::parse(s).unwrap());
+    assert!(validate("https://example.com/").is_ok());
+    assert!(validate("FILE:///tmp").is_ok());
+    assert!(validate("mailto:a@example.com").is_ok());
+    assert!(validate("ftp://u@example.com:99999/").is_ok());
+    assert!(validate("https://u@example.com/").is_err());
+    assert!(validate("file:///tmp?x").is_err());
+    assert!(validate("mailto:a").is_err());
+
+    let uri = Uri::parse(String::from("https://example.com/")).unwrap();
+    assert!(registry.validate(&uri).is_ok());
+
+    // Later registrations take precedence.
+    let registry = SchemeRegistry::builtin().register::<StrictHttp>();
+    let e = registry
+        .validate(&Uri::parse("http://example.com/").unwrap())
+        .unwrap_err();
+    assert_eq!(e.to_string(), "URI violates scheme-specific syntax");
+    assert!(registry
+        .validate(&Uri::parse("https://example.com/").unwrap())
+        .is_ok());
+
+    assert_eq!(
+        format!("{:?}", SchemeRegistry::builtin()),
+        r#"{"http", "https", "file"}"#
+    );
+}
+
+#[test]
+fn build_with() {
+    let uri = Uri::builder()
+        .scheme(Scheme::new_or_panic("https"))
+        .authority_with(|b| b.host(EStr::new_or_panic("example.com")))
+        .path(EStr::new_or_panic("/a"))
+        .build_with::<Http>()
+        .unwrap();
+    assert_eq!(uri, "https://example.com/a");
+    assert_eq!(uri.port_u16(), 443);
+
+    let e = Uri::builder()
+        .scheme(Scheme::new_or_panic("https"))
+        .authority_with(|b| b.host(EStr::new_or_panic("example.com")))
+        .path(EStr::new_or_panic("a"))
+        .build_with::<Http>()
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "path must either be empty or start with '/' when authority is present"
+    );
+
+    let registry = SchemeRegistry::builtin();
+    let e = Uri::builder()
+        .scheme(Scheme::new_or_panic("file"))
+        .authority_with(|b| b.host(EStr::new_or_panic("host")).port(21))
+        .path(EStr::new_or_panic("/a"))
+        .build_with_registry(&registry)
+        .unwrap_err();
+    assert_eq!(e.to_string(), "file URI with port");
+
+    let uri = Uri::builder()
+        .scheme(Scheme::new_or_panic("urn"))
+        .path(EStr::new_or_panic("example:a"))
+        .build_with_registry(&registry)
+        .unwrap();
+    assert_eq!(uri, "urn:example:a");
+}
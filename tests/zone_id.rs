@@ -0,0 +1,122 @@
+#![cfg(all(feature = "zone-id", feature = "net"))]
+
+use fluent_uri::{component::Host, encoding::EStr, Uri, UriRef};
+use std::net::Ipv6Addr;
+
+fn zone_id(s: &str) -> Option<&str> {
+    let Host::Ipv6(_, zone_id) = Host::parse(s).unwrap() else {
+        panic!("not an IPv6 address");
+    };
+    let r = UriRef::parse(format!("//{s}")).unwrap();
+    assert_eq!(r.authority().unwrap().zone_id(), zone_id);
+    zone_id.map(EStr::as_str)
+}
+
+#[test]
+fn parse() {
+    assert_eq!(zone_id("[fe80::1%25eth0]"), Some("eth0"));
+    assert_eq!(zone_id("[fe80::1%252]"), Some("2"));
+    assert_eq!(zone_id("[fe80::1%25en%2D1]"), Some("en%2D1"));
+    assert_eq!(zone_id("[::ffff:1.2.3.4%25x]"), Some("x"));
+    assert_eq!(zone_id("[fe80::1]"), None);
+
+    assert!(matches!(
+        Host::parse("[fe80::1%25eth0]").unwrap(),
+        Host::Ipv6(addr, Some(z)) if addr == Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1) && z == "eth0"
+    ));
+    assert!(matches!(
+        Host::parse("[fe80::1]").unwrap(),
+        Host::Ipv6(_, None)
+    ));
+
+    let uri = Uri::parse("http://[fe80::a%25en1]:8080/path").unwrap();
+    let auth = uri.authority().unwrap();
+    assert_eq!(auth.host(), "[fe80::a%25en1]");
+    assert_eq!(auth.port().unwrap(), "8080");
+    assert_eq!(uri.spans().host, Some(7..22));
+
+    let r = UriRef::parse("//u@[fe80::1%25eth0]").unwrap();
+    assert_eq!(r.authority().unwrap().host(), "[fe80::1%25eth0]");
+
+    // Bare '%', empty zone, and invalid characters.
+    assert!(Host::parse("[fe80::1%eth0]").is_err());
+    assert!(Host::parse("[fe80::1%25]").is_err());
+    assert!(Host::parse("[fe80::1%25eth0/]").is_err());
+    assert!(Host::parse("[fe80::1%25eth%]").is_err());
+    assert!(Host::parse("[fe80::1%25eth0").is_err());
+    // Zone identifiers only follow IPv6 addresses.
+    assert!(Host::parse("[v1.x%25eth0]").is_err());
+    assert!(Host::parse("1.2.3.4%25eth0").is_ok_and(|h| matches!(h, Host::RegName(_))));
+}
+
+#[test]
+fn normalize() {
+    let uri = Uri::parse("HTTP://[FE80:0::1%25En%2d1%2F]/").unwrap();
+    assert_eq!(uri.normalize(), "http://[fe80::1%25En-1%2F]/");
+
+    let (normalized, map) = uri.normalize_with_map();
+    assert_eq!(normalized, "http://[fe80::1%25En-1%2F]/");
+    assert_eq!(
+        map.map_range(7..14),
+        Some((fluent_uri::map::Source::Input, 7..17))
+    );
+}
+
+#[test]
+fn build() {
+    let zone_id = EStr::new_or_panic("eth0");
+    let uri = Uri::builder()
+        .scheme(fluent_uri::component::Scheme::new_or_panic("http"))
+        .authority_with(|b| b.host((Ipv6Addr::LOCALHOST, zone_id)).port(80))
+        .path(EStr::EMPTY)
+        .build()
+        .unwrap();
+    assert_eq!(uri, "http://[::1%25eth0]:80");
+    let auth = uri.authority().unwrap();
+    assert!(matches!(
+        auth.host_parsed(),
+        Host::Ipv6(Ipv6Addr::LOCALHOST, Some(z)) if z == "eth0"
+    ));
+    assert_eq!(auth.zone_id().unwrap(), "eth0");
+
+    for zone_id in ["2", "en%2D1", "%25"] {
+        let uri = Uri::builder()
+            .scheme(fluent_uri::component::Scheme::new_or_panic("http"))
+            .authority_with(|b| b.host((Ipv6Addr::LOCALHOST, EStr::new_or_panic(zone_id))))
+            .path(EStr::EMPTY)
+            .build()
+            .unwrap();
+        let parsed = Uri::parse(uri.as_str()).unwrap();
+        assert_eq!(parsed, uri);
+        assert_eq!(parsed.authority().unwrap().zone_id().unwrap(), zone_id);
+    }
+
+    let e = Uri::builder()
+        .scheme(fluent_uri::component::Scheme::new_or_panic("http"))
+        .authority_with(|b| b.host((Ipv6Addr::LOCALHOST, EStr::EMPTY)))
+        .path(EStr::EMPTY)
+        .build()
+        .unwrap_err();
+    assert_eq!(e.to_string(), "empty zone identifier in IPv6 address");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn socket_addrs() {
+    use std::{io, net::SocketAddrV6};
+
+    let uri = Uri::parse("http://[fe80::1%253]:8080/").unwrap();
+    let addrs: Vec<_> = uri.authority().unwrap().socket_addrs(80).unwrap().collect();
+    let addr = SocketAddrV6::new(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), 8080, 0, 3);
+    assert_eq!(addrs, [addr.into()]);
+
+    let uri = Uri::parse("http://[fe80::1%25%34%32]/").unwrap();
+    let addrs: Vec<_> = uri.authority().unwrap().socket_addrs(80).unwrap().collect();
+    assert_eq!(addrs[0].to_string(), "[fe80::1%42]:80");
+
+    let uri = Uri::parse("http://[fe80::1%25eth0]/").unwrap();
+    let Err(e) = uri.authority().unwrap().socket_addrs(80) else {
+        panic!("expected an error");
+    };
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+}